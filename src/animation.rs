@@ -0,0 +1,315 @@
+#![allow(dead_code)]
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::canvas::*;
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::raster::{self, RasterOptions};
+
+/// Stands in for a grid while a generator runs over it, remembering every
+/// passage carved in order.
+pub struct Recorder<'a, G: MazeGrid> {
+    pub grid: &'a mut G,
+    pub steps: Vec<(CellLink, CellLink)>,
+}
+
+impl<'a, G: MazeGrid> Recorder<'a, G> {
+    pub fn new(grid: &'a mut G) -> Self {
+        Recorder {
+            grid,
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl<G: MazeGrid> MazeGrid for Recorder<'_, G> {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.each_row()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        self.grid.random_cell()
+    }
+
+    fn size(&self) -> usize {
+        self.grid.size()
+    }
+
+    fn neighbors(&self, cell: &CellLink) -> Vec<CellLink> {
+        self.grid.neighbors(cell)
+    }
+
+    fn link(&mut self, cell1: CellLink, cell2: CellLink) {
+        self.steps.push((cell1.clone(), cell2.clone()));
+        self.grid.link(cell1, cell2);
+    }
+
+    fn run_exit(&self, cell: &CellLink) -> Option<CellLink> {
+        self.grid.run_exit(cell)
+    }
+
+    fn run_next(&self, cell: &CellLink) -> Option<CellLink> {
+        self.grid.run_next(cell)
+    }
+
+    fn each_cell(&self) -> Vec<CellLink> {
+        self.grid.each_cell()
+    }
+
+    fn dead_ends(&self) -> Vec<CellLink> {
+        self.grid.dead_ends()
+    }
+}
+
+/// Frames to be written out as an animated GIF.
+pub struct Animation {
+    /// Each frame with how long it shows, in hundredths of a second.
+    pub frames: Vec<(Canvas, u16)>,
+    pub delay: u16,
+}
+
+impl Animation {
+    pub fn new(delay: u16) -> Self {
+        Animation {
+            frames: Vec::new(),
+            delay,
+        }
+    }
+
+    pub fn push(&mut self, canvas: Canvas) {
+        self.frames.push((canvas, self.delay));
+    }
+
+    /// Keeps the last frame up for `delay` instead.
+    pub fn hold(&mut self, delay: u16) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.1 = delay;
+        }
+    }
+
+    /// Adds a frame for every `per_frame` of the recorded `steps` as they
+    /// carve `grid`, which must be the grid they were recorded on. The links
+    /// are undone first and redone one by one, so the grid ends up as it was.
+    pub fn carving(
+        &mut self,
+        grid: &Grid,
+        steps: &[(CellLink, CellLink)],
+        per_frame: usize,
+        options: &RasterOptions,
+    ) {
+        for (cell1, cell2) in steps {
+            unlink(cell1.clone(), cell2.clone());
+        }
+
+        self.push(raster::render(grid, options));
+        for (index, (cell1, cell2)) in steps.iter().enumerate() {
+            link(cell1.clone(), cell2.clone());
+            if (index + 1) % per_frame.max(1) == 0 || index + 1 == steps.len() {
+                self.push(raster::render(grid, options));
+            }
+        }
+    }
+
+    /// Adds frames of a flood fill spreading out from `start` one step at a
+    /// time, then of the solution to `goal` growing. A `goal` the flood
+    /// never reaches gets no solution frames. `grid` keeps the full
+    /// distances from `start` afterwards.
+    pub fn solving(
+        &mut self,
+        grid: &mut Grid,
+        start: CellLink,
+        goal: CellLink,
+        options: &RasterOptions,
+    ) {
+        let distances = Distance::distances(start.clone());
+        let path = if distances.get(goal.clone()).is_some() {
+            distances
+                .path_to(goal)
+                .ordered()
+                .iter()
+                .map(|v| (v.row, v.column))
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        for frontier in 0..=distances.max() {
+            let mut reached = Distance::new(start.clone());
+            for (cell, distance) in &distances.cells {
+                if *distance <= frontier {
+                    reached.cells.insert(cell.clone(), *distance);
+                }
+            }
+            grid.set_distance(reached);
+            self.push(raster::render(grid, options));
+        }
+        grid.set_distance(distances);
+
+        for length in 1..=path.len() {
+            let options = RasterOptions {
+                solution: path[..length].to_vec(),
+                ..*options
+            };
+            self.push(raster::render(grid, &options));
+        }
+    }
+
+    /// An error when a frame is wider or taller than a GIF can be.
+    pub fn write_gif<W: Write>(&self, writer: W) -> io::Result<()> {
+        let Some((first, _)) = self.frames.first() else {
+            return Ok(());
+        };
+        let (width, height) = gif_size(first)?;
+
+        let mut encoder = gif::Encoder::new(writer, width, height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        for (canvas, delay) in &self.frames {
+            let mut frame = gif_frame(canvas, gif_size(canvas)?);
+            frame.delay = *delay;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+
+    pub fn save_gif<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_gif(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// GIF sizes are 16 bits each way.
+fn gif_size(canvas: &Canvas) -> io::Result<(u16, u16)> {
+    match (u16::try_from(canvas.width), u16::try_from(canvas.height)) {
+        (Ok(width), Ok(height)) => Ok((width, height)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a GIF frame is at most 65535 pixels each way",
+        )),
+    }
+}
+
+/// Maze frames rarely use more than a GIF's 256 colors, so each gets an
+/// exact palette of its own; anything busier is quantized.
+fn gif_frame(canvas: &Canvas, (width, height): (u16, u16)) -> gif::Frame<'static> {
+    let mut indices: HashMap<Color, u8> = HashMap::new();
+    let mut palette: Vec<u8> = Vec::new();
+    let mut pixels: Vec<u8> = Vec::with_capacity(canvas.pixels().len());
+
+    for color in canvas.pixels() {
+        let index = match indices.get(color) {
+            Some(index) => *index,
+            None if indices.len() < 256 => {
+                let index = indices.len() as u8;
+                indices.insert(*color, index);
+                palette.extend([color.r, color.g, color.b]);
+                index
+            }
+            None => return gif::Frame::from_rgb_speed(width, height, &canvas.rgb(), 10),
+        };
+        pixels.push(index);
+    }
+
+    gif::Frame::from_palette_pixels(width, height, pixels, palette, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recursive_backtracker;
+    use crate::topology::Topology;
+
+    /// Each cell with the cells it is linked to, in order.
+    type Links = Vec<((usize, usize), Vec<(usize, usize)>)>;
+
+    fn links(grid: &Grid) -> Links {
+        grid.each_cell()
+            .iter()
+            .map(|v| {
+                let cell = v.borrow();
+                let mut links = cell
+                    .links
+                    .iter()
+                    .filter_map(|v| v.clone().upgrade())
+                    .map(|v| (v.borrow().row, v.borrow().column))
+                    .collect::<Vec<_>>();
+                links.sort();
+                ((cell.row, cell.column), links)
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn recorded() -> (Grid, Vec<(CellLink, CellLink)>) {
+        let mut grid = Grid::new(4, 5);
+        grid.configure_cells();
+        let mut recorder = Recorder::new(&mut grid);
+        recursive_backtracker::on(&mut recorder);
+        let steps = recorder.steps;
+        (grid, steps)
+    }
+
+    #[test]
+    fn carving_leaves_the_maze_as_it_was() {
+        let (grid, steps) = recorded();
+        assert_eq!(steps.len(), 19);
+        let before = links(&grid);
+
+        let mut animation = Animation::new(5);
+        animation.carving(&grid, &steps, 4, &RasterOptions::default());
+        assert_eq!(links(&grid), before);
+        // The empty grid, then every four steps and the last one.
+        assert_eq!(animation.frames.len(), 1 + 19 / 4 + 1);
+    }
+
+    #[test]
+    fn gif_holds_every_frame() {
+        let (mut grid, steps) = recorded();
+        let mut animation = Animation::new(5);
+        animation.carving(&grid, &steps, 1, &RasterOptions::default());
+        let (start, goal) = (grid.get_cell(0, 0).unwrap(), grid.get_cell(3, 4).unwrap());
+        animation.solving(&mut grid, start, goal, &RasterOptions::default());
+        animation.hold(100);
+
+        let mut gif = Vec::new();
+        animation.write_gif(&mut gif).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(&gif[..]).unwrap();
+        let first = &animation.frames[0].0;
+        assert_eq!(
+            (decoder.width() as usize, decoder.height() as usize),
+            (first.width, first.height)
+        );
+        let mut delays = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        assert_eq!(delays.len(), animation.frames.len());
+        assert_eq!(delays.last(), Some(&100));
+    }
+
+    #[test]
+    fn unreachable_goal_gets_no_solution_frames() {
+        let mut grid = Grid::new(2, 2);
+        grid.configure_cells();
+        let (start, goal) = (grid.get_cell(0, 0).unwrap(), grid.get_cell(1, 1).unwrap());
+        let mut animation = Animation::new(5);
+        animation.solving(&mut grid, start, goal, &RasterOptions::default());
+        assert_eq!(animation.frames.len(), 1);
+    }
+
+    #[test]
+    fn oversized_frames_are_an_error() {
+        let mut animation = Animation::new(5);
+        animation.push(Canvas::new(70_000, 1, Color::rgb(255, 255, 255)));
+        let error = animation.write_gif(Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,170 @@
+#![allow(dead_code)]
+use std::env;
+use std::io::{self, IsTerminal};
+
+use super::canvas::Color;
+use super::grid::*;
+use super::palette::*;
+use super::unicode;
+
+/// How much color the terminal can show.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorMode {
+    /// 24-bit `ESC[48;2;r;g;bm` escapes.
+    TrueColor,
+    /// The xterm 256-color palette.
+    Ansi256,
+    /// No escapes at all, for pipes, files and `NO_COLOR`.
+    Plain,
+}
+
+impl ColorMode {
+    /// Plain unless stdout is a terminal; true color when `COLORTERM` says
+    /// the terminal supports it.
+    pub fn detect() -> Self {
+        if !io::stdout().is_terminal() || env::var_os("NO_COLOR").is_some() {
+            return ColorMode::Plain;
+        }
+        match env::var("COLORTERM") {
+            Ok(v) if v == "truecolor" || v == "24bit" => ColorMode::TrueColor,
+            _ => ColorMode::Ansi256,
+        }
+    }
+
+    fn escape(&self, layer: u8, color: Color) -> String {
+        match self {
+            ColorMode::TrueColor => {
+                format!("\x1b[{};2;{};{};{}m", layer, color.r, color.g, color.b)
+            }
+            ColorMode::Ansi256 => {
+                // The nearest entry of the 6x6x6 color cube.
+                let level = |v: u8| (v as u16 * 5 + 127) / 255;
+                let index = 16 + 36 * level(color.r) + 6 * level(color.g) + level(color.b);
+                format!("\x1b[{};5;{}m", layer, index)
+            }
+            ColorMode::Plain => String::new(),
+        }
+    }
+
+    pub fn background(&self, color: Color) -> String {
+        self.escape(48, color)
+    }
+
+    pub fn foreground(&self, color: Color) -> String {
+        self.escape(38, color)
+    }
+
+    pub fn reset(&self) -> &'static str {
+        match self {
+            ColorMode::Plain => "",
+            _ => "\x1b[0m",
+        }
+    }
+}
+
+pub struct AnsiOptions {
+    pub mode: ColorMode,
+    /// Colors cell backgrounds by the grid's `distance`, when it has one.
+    pub palette: Palette,
+    /// Cells the solution passes through, marked with a dot.
+    pub solution: Vec<(usize, usize)>,
+    pub solution_color: Color,
+}
+
+impl Default for AnsiOptions {
+    fn default() -> Self {
+        AnsiOptions {
+            mode: ColorMode::detect(),
+            palette: Palette::Viridis,
+            solution: Vec::new(),
+            solution_color: Color::rgb(255, 60, 60),
+        }
+    }
+}
+
+/// Draws a square grid with box-drawing walls and each cell's background
+/// colored by distance. Without color the distances are written out as in
+/// `unicode::render`, so the output stays readable in a pipe.
+pub fn render(grid: &Grid, options: &AnsiOptions) -> String {
+    let mode = options.mode;
+    let max = grid.distance.as_ref().map_or(0, |v| v.max());
+
+    unicode::render_with(grid, 3, |row, column| {
+        let on_path = options.solution.contains(&(row, column));
+        let distance = match (&grid.distance, grid.get_cell(row, column)) {
+            (Some(distance), Some(cell)) => distance.get(cell),
+            _ => None,
+        };
+
+        if mode == ColorMode::Plain {
+            let text = if on_path {
+                String::from("•")
+            } else {
+                super::distance::distance_str(distance)
+            };
+            return format!(" {} ", text);
+        }
+
+        let mut text = match distance {
+            Some(distance) => mode.background(options.palette.distance_color(distance, max)),
+            None => String::new(),
+        };
+        if on_path {
+            text.push_str(&mode.foreground(options.solution_color));
+            text.push_str(" ● ");
+        } else {
+            text.push_str("   ");
+        }
+        text.push_str(mode.reset());
+        text
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::Distance;
+    use crate::recursive_backtracker;
+    use crate::topology::Topology;
+
+    fn render_in(mode: ColorMode) -> String {
+        let mut grid = Grid::new(4, 5);
+        grid.configure_cells();
+        recursive_backtracker::on(&mut grid);
+        let distance = Distance::distances(grid.get_cell(0, 0).unwrap());
+        grid.set_distance(distance);
+
+        let options = AnsiOptions {
+            mode,
+            solution: vec![(0, 0), (1, 0), (3, 4)],
+            ..Default::default()
+        };
+        render(&grid, &options)
+    }
+
+    #[test]
+    fn plain_output_has_no_escapes() {
+        let output = render_in(ColorMode::Plain);
+        assert!(!output.contains('\x1b'));
+        assert_eq!(output.matches('•').count(), 3);
+    }
+
+    #[test]
+    fn colored_output_marks_the_solution() {
+        for mode in [ColorMode::TrueColor, ColorMode::Ansi256] {
+            let output = render_in(mode);
+            assert!(output.contains("\x1b["));
+            assert_eq!(output.matches('●').count(), 3, "{:?}", mode);
+            assert_eq!(output.matches("\x1b[0m").count(), 20, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn escapes_pick_the_nearest_color() {
+        let red = Color::rgb(255, 0, 0);
+        assert_eq!(ColorMode::TrueColor.background(red), "\x1b[48;2;255;0;0m");
+        assert_eq!(ColorMode::Ansi256.foreground(red), "\x1b[38;5;196m");
+        assert_eq!(ColorMode::Ansi256.background(Color::rgb(0, 0, 0)), "\x1b[48;5;16m");
+        assert_eq!(ColorMode::Plain.background(red), "");
+    }
+}
@@ -0,0 +1,184 @@
+#![allow(dead_code)]
+use super::grid::*;
+use super::grid3d::*;
+use super::triangle_grid::*;
+use super::weave_grid::*;
+
+/// Draws a square grid in `+---+` ASCII around whatever `contents` puts in
+/// each cell; it should fill `cell_width` columns.
+pub fn render_with<F: Fn(usize, usize) -> String>(
+    grid: &Grid,
+    cell_width: usize,
+    contents: F,
+) -> String {
+    let (horizontal, vertical) = grid.walls();
+    let wall = "-".repeat(cell_width);
+    let open = " ".repeat(cell_width);
+
+    let mut output = String::new();
+    for row in 0..=grid.rows {
+        for wall_above in &horizontal[row] {
+            output.push('+');
+            output.push_str(if *wall_above { &wall } else { &open });
+        }
+        output.push_str("+\n");
+
+        if row == grid.rows {
+            break;
+        }
+
+        for (column, walls) in vertical.iter().enumerate() {
+            output.push(if walls[row] { '|' } else { ' ' });
+            if column < grid.columns {
+                output.push_str(&contents(row, column));
+            }
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Draws the levels of a 3D grid side by side, lowest first, around a one
+/// column `contents` for each cell (by its stacked `row`). Stairs are
+/// marked either side of it: `v` on the left goes down a level, `^` on the
+/// right goes up.
+pub fn render_3d<F: Fn(usize, usize) -> String>(grid: &Grid3D, contents: F) -> String {
+    let gap = "   ";
+    let border = format!("{}+", "+---".repeat(grid.columns));
+    let mut output = vec![border; grid.levels].join(gap);
+    output.push('\n');
+
+    for row in 0..grid.rows {
+        let mut top_strs: Vec<String> = Vec::with_capacity(grid.levels);
+        let mut bottom_strs: Vec<String> = Vec::with_capacity(grid.levels);
+
+        for level in 0..grid.levels {
+            let mut top_str = String::from("|");
+            let mut bottom_str = String::from("+");
+            for cell in &grid.grid[level * grid.rows + row] {
+                let cell = cell.borrow();
+                top_str.push(if cell.walled(grid.down(&cell)) { ' ' } else { 'v' });
+                top_str.push_str(&contents(cell.row, cell.column));
+                top_str.push(if cell.walled(grid.up(&cell)) { ' ' } else { '^' });
+                top_str.push(if cell.walled(grid.east(&cell)) { '|' } else { ' ' });
+                bottom_str.push_str(if cell.walled(grid.south(&cell)) {
+                    "---"
+                } else {
+                    "   "
+                });
+                bottom_str.push('+');
+            }
+            top_strs.push(top_str);
+            bottom_strs.push(bottom_str);
+        }
+
+        output.push_str(&top_strs.join(gap));
+        output.push('\n');
+        output.push_str(&bottom_strs.join(gap));
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Draws a triangle grid. Each row takes two lines and each triangle four
+/// characters, with neighbors overlapping by two, so there is no room for
+/// anything inside:
+///
+/// ```text
+///    __
+///  /\  /\
+/// /__\/__\
+/// ```
+pub fn render_triangles(grid: &TriangleGrid) -> String {
+    let width = grid.columns * 2 + 2;
+    let mut canvas = vec![vec![' '; width]; grid.rows * 2 + 1];
+
+    for row in &grid.grid {
+        for cell in row {
+            let cell = cell.borrow();
+            let x = cell.column * 2;
+            let top = cell.row * 2;
+
+            if upright(&cell) {
+                if cell.walled(grid.west(&cell)) {
+                    canvas[top + 1][x + 1] = '/';
+                    canvas[top + 2][x] = '/';
+                }
+                if cell.walled(grid.east(&cell)) {
+                    canvas[top + 1][x + 2] = '\\';
+                    canvas[top + 2][x + 3] = '\\';
+                }
+                if cell.walled(grid.south(&cell)) {
+                    canvas[top + 2][x + 1] = '_';
+                    canvas[top + 2][x + 2] = '_';
+                }
+            } else {
+                if cell.walled(grid.west(&cell)) {
+                    canvas[top + 1][x] = '\\';
+                    canvas[top + 2][x + 1] = '\\';
+                }
+                if cell.walled(grid.east(&cell)) {
+                    canvas[top + 1][x + 3] = '/';
+                    canvas[top + 2][x + 2] = '/';
+                }
+                if cell.walled(grid.north(&cell)) {
+                    canvas[top][x + 1] = '_';
+                    canvas[top][x + 2] = '_';
+                }
+            }
+        }
+    }
+
+    canvas
+        .into_iter()
+        .map(|line| format!("{}\n", line.into_iter().collect::<String>().trim_end()))
+        .collect::<String>()
+}
+
+/// Draws a weave grid like a square one, except that a crossing shows
+/// which way the top passage runs instead of `contents`: `<=>` east-west
+/// over a tunnel, ` H ` north-south.
+pub fn render_weave<F: Fn(usize, usize) -> String>(
+    grid: &WeaveGrid,
+    cell_width: usize,
+    contents: F,
+) -> String {
+    let square = &grid.grid;
+    let mut output = format!("{}+\n", format!("+{}", "-".repeat(cell_width)).repeat(square.columns));
+
+    for row in &square.grid {
+        let mut top_str = String::from("|");
+        let mut bottom_str = String::from("+");
+        for cell in row {
+            let cell_borrowed = cell.borrow();
+            let body = if grid.under_cell(&cell_borrowed).is_none() {
+                contents(cell_borrowed.row, cell_borrowed.column)
+            } else if grid.horizontal_passage(&cell_borrowed) {
+                format!("<{}>", "=".repeat(cell_width.saturating_sub(2)))
+            } else {
+                format!("{:^width$}", "H", width = cell_width)
+            };
+            top_str.push_str(&body);
+
+            match square.east(&cell_borrowed) {
+                Some(east) if grid.connected(cell, &east) => top_str.push(' '),
+                _ => top_str.push('|'),
+            }
+            match square.south(&cell_borrowed) {
+                Some(south) if grid.connected(cell, &south) => {
+                    bottom_str.push_str(&" ".repeat(cell_width))
+                }
+                _ => bottom_str.push_str(&"-".repeat(cell_width)),
+            }
+            bottom_str.push('+');
+        }
+        output.push_str(&top_str);
+        output.push('\n');
+        output.push_str(&bottom_str);
+        output.push('\n');
+    }
+
+    output
+}
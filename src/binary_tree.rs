@@ -1,31 +1,50 @@
 #![allow(dead_code)]
 use super::cell::*;
 use super::grid::*;
+use super::random;
 
 use rand::Rng;
 
-pub fn on(grid: &mut Grid) {
-    let mut rng = rand::thread_rng();
-    for row in &grid.grid {
-        for cell in row {
+pub fn on<G: MazeGrid>(grid: &mut G) {
+    let mut rng = random::rng();
+    for row in grid.each_row() {
+        for cell in &row {
             // let cell = cell.clone();
             let mut neighbors: Vec<CellLink> = Vec::new();
-            {
-                let cell_borrowed = cell.borrow();
-
-                if let Some(north) = cell_borrowed.north() {
-                    neighbors.push(north);
-                }
-                if let Some(east) = cell_borrowed.east() {
-                    neighbors.push(east);
-                }
+            if let Some(exit) = grid.run_exit(cell) {
+                neighbors.push(exit);
+            }
+            if let Some(next) = grid.run_next(cell) {
+                neighbors.push(next);
             }
 
             if !neighbors.is_empty() {
                 let index = rng.gen_range(0..neighbors.len());
 
-                link(cell.clone(), neighbors[index].clone());
+                grid.link(cell.clone(), neighbors[index].clone());
             }
         }
     }
+
+    // A mask can leave cells with neither an exit nor a next cell.
+    join_regions(grid);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::tests::{assert_spanning_tree, assert_spans_every_grid, holed_grid};
+
+    #[test]
+    fn carves_a_spanning_tree_on_every_grid() {
+        assert_spans_every_grid!(super::on);
+    }
+
+    #[test]
+    fn carves_around_masked_cells() {
+        for _ in 0..20 {
+            let mut grid = holed_grid();
+            super::on(&mut grid);
+            assert_spanning_tree(&grid, "masked");
+        }
+    }
 }
@@ -0,0 +1,73 @@
+#![allow(dead_code)]
+use super::grid::*;
+use super::random;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Links each dead end to one of its neighbors with probability `p`,
+/// preferring neighbors that are dead ends as well.
+pub fn braid<G: MazeGrid>(grid: &mut G, p: f64) {
+    let mut rng = random::rng();
+    let mut dead_ends = grid.dead_ends();
+    dead_ends.shuffle(&mut rng);
+
+    for cell in dead_ends {
+        // An earlier iteration may already have linked this cell.
+        if cell.borrow().links.len() != 1 || rng.gen::<f64>() >= p {
+            continue;
+        }
+
+        let neighbors = grid
+            .neighbors(&cell)
+            .into_iter()
+            .filter(|v| cell.borrow().linked(v.clone()).is_none())
+            .collect::<Vec<_>>();
+
+        let best = neighbors
+            .iter()
+            .filter(|v| v.borrow().links.len() == 1)
+            .cloned()
+            .collect::<Vec<_>>();
+        let best = if best.is_empty() { neighbors } else { best };
+
+        if let Some(neighbor) = best.choose(&mut rng) {
+            grid.link(cell.clone(), neighbor.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recursive_backtracker;
+    use crate::topology::Topology;
+
+    fn maze() -> Grid {
+        let mut grid = Grid::new(8, 8);
+        grid.configure_cells();
+        recursive_backtracker::on(&mut grid);
+        grid
+    }
+
+    fn links(grid: &Grid) -> usize {
+        grid.each_cell().iter().map(|v| v.borrow().links.len()).sum()
+    }
+
+    #[test]
+    fn never_braids_at_zero() {
+        let mut grid = maze();
+        let (dead_ends, before) = (grid.dead_ends().len(), links(&grid));
+        braid(&mut grid, 0.0);
+        assert_eq!(grid.dead_ends().len(), dead_ends);
+        assert_eq!(links(&grid), before);
+    }
+
+    #[test]
+    fn always_braids_at_one() {
+        let mut grid = maze();
+        assert!(!grid.dead_ends().is_empty());
+        braid(&mut grid, 1.0);
+        assert!(grid.dead_ends().is_empty());
+    }
+}
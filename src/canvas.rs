@@ -0,0 +1,171 @@
+#![allow(dead_code)]
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+
+    /// As written in SVG and HTML, e.g. `#ff8000`.
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// An RGB image to draw mazes on, saved as PNG.
+pub struct Canvas {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Pixels off the canvas are ignored.
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Color) {
+        for y in y..(y + height).min(self.height) {
+            for x in x..(x + width).min(self.width) {
+                self.pixels[y * self.width + x] = color;
+            }
+        }
+    }
+
+    /// Fills every pixel whose center lies inside `points`, even-odd rule.
+    pub fn fill_polygon(&mut self, points: &[(f64, f64)], color: Color) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let top = points.iter().map(|v| v.1).fold(f64::MAX, f64::min);
+        let bottom = points.iter().map(|v| v.1).fold(f64::MIN, f64::max);
+        let first = top.max(0.0).floor() as usize;
+        let last = (bottom.ceil().max(0.0) as usize).min(self.height);
+
+        for y in first..last {
+            let center = y as f64 + 0.5;
+            let mut crossings = Vec::new();
+            for (index, (x1, y1)) in points.iter().enumerate() {
+                let (x2, y2) = points[(index + 1) % points.len()];
+                if (*y1 <= center) != (y2 <= center) {
+                    crossings.push(x1 + (center - y1) / (y2 - y1) * (x2 - x1));
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+
+            for span in crossings.chunks(2) {
+                if let [from, to] = span {
+                    let from = (from - 0.5).ceil().max(0.0) as usize;
+                    let to = ((to - 0.5).floor() + 1.0).max(0.0) as usize;
+                    for x in from..to.min(self.width) {
+                        self.pixels[y * self.width + x] = color;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fills every pixel whose center lies inside the circle.
+    pub fn fill_circle(&mut self, center: (f64, f64), radius: f64, color: Color) {
+        let top = (center.1 - radius).floor().max(0.0) as usize;
+        let bottom = ((center.1 + radius).ceil().max(0.0) as usize).min(self.height);
+        let left = (center.0 - radius).floor().max(0.0) as usize;
+        let right = ((center.0 + radius).ceil().max(0.0) as usize).min(self.width);
+
+        for y in top..bottom {
+            for x in left..right {
+                let (dx, dy) = (x as f64 + 0.5 - center.0, y as f64 + 0.5 - center.1);
+                if dx * dx + dy * dy <= radius * radius {
+                    self.pixels[y * self.width + x] = color;
+                }
+            }
+        }
+    }
+
+    /// Draws a line `thickness` pixels wide with flat ends.
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), thickness: f64, color: Color) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0.0 {
+            return;
+        }
+
+        let (nx, ny) = (-dy / length * thickness / 2.0, dx / length * thickness / 2.0);
+        self.fill_polygon(
+            &[
+                (from.0 + nx, from.1 + ny),
+                (to.0 + nx, to.1 + ny),
+                (to.0 - nx, to.1 - ny),
+                (from.0 - nx, from.1 - ny),
+            ],
+            color,
+        );
+    }
+
+    /// Draws connected lines with round joins and ends.
+    pub fn polyline(&mut self, points: &[(f64, f64)], thickness: f64, color: Color) {
+        for step in points.windows(2) {
+            self.line(step[0], step[1], thickness, color);
+        }
+        for point in points {
+            self.fill_circle(*point, thickness / 2.0, color);
+        }
+    }
+
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// Three bytes a pixel, row by row.
+    pub fn rgb(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|v| [v.r, v.g, v.b])
+            .collect::<Vec<_>>()
+    }
+
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.rgb())?;
+        Ok(())
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_png(BufWriter::new(File::create(path)?))
+    }
+}
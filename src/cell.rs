@@ -29,10 +29,7 @@ pub trait Upgradable<T> {
 
 impl Upgradable<Option<CellLink>> for Option<WeakCellLink> {
     fn upgrade(self) -> Option<CellLink> {
-        match self.map(|v| v.upgrade()) {
-            None => None,
-            Some(v) => v,
-        }
+        self.map(|v| v.upgrade()).unwrap_or_default()
     }
 }
 
@@ -40,10 +37,7 @@ impl Upgradable<Option<CellLink>> for Option<WeakCellLink> {
 pub struct Cell {
     pub row: usize,
     pub column: usize,
-    pub north: Option<WeakCellLink>,
-    pub south: Option<WeakCellLink>,
-    pub east: Option<WeakCellLink>,
-    pub west: Option<WeakCellLink>,
+    pub neighbors: Vec<WeakCellLink>,
     pub links: Vec<WeakCellLink>,
 }
 
@@ -60,40 +54,18 @@ impl Hash for Cell {
     }
 }
 
-impl Eq for Cell {
-    fn assert_receiver_is_total_eq(&self) {
-    }
-}
+impl Eq for Cell {}
 
 impl Cell {
     pub fn new(row: usize, column: usize) -> Self {
         Cell {
             row,
             column,
-            north: None,
-            south: None,
-            east: None,
-            west: None,
+            neighbors: Vec::new(),
             links: Vec::new(),
         }
     }
 
-    pub fn north(&self) -> Option<CellLink> {
-        self.north.clone().upgrade()
-    }
-
-    pub fn south(&self) -> Option<CellLink> {
-        self.south.clone().upgrade()
-    }
-
-    pub fn east(&self) -> Option<CellLink> {
-        self.east.clone().upgrade()
-    }
-
-    pub fn west(&self) -> Option<CellLink> {
-        self.west.clone().upgrade()
-    }
-
     pub fn linked(&self, cell: CellLink) -> Option<CellLink> {
         let result = self.links.iter().find(|v| match v.upgrade() {
             Some(v) => *v.borrow() == *cell.borrow(),
@@ -105,21 +77,18 @@ impl Cell {
         }
     }
 
-    pub fn neighbors(&self) -> Vec<CellLink> {
-        let mut result: Vec<CellLink> = Vec::new();
-        if let Some(cell) = self.north() {
-            result.push(cell);
+    /// Whether there is a wall towards `neighbor`; a missing neighbor is the
+    /// outer wall.
+    pub fn walled(&self, neighbor: Option<CellLink>) -> bool {
+        match neighbor {
+            Some(neighbor) => self.linked(neighbor).is_none(),
+            None => true,
         }
-        if let Some(cell) = self.south() {
-            result.push(cell);
-        }
-        if let Some(cell) = self.east() {
-            result.push(cell);
-        }
-        if let Some(cell) = self.west() {
-            result.push(cell);
-        }
-        result
+    }
+
+    /// The cells bordering this one, as wired up by the grid's `Topology`.
+    pub fn neighbors(&self) -> Vec<CellLink> {
+        self.neighbors.iter().filter_map(|v| v.upgrade()).collect::<Vec<_>>()
     }
 }
 
@@ -129,25 +98,37 @@ pub fn link(cell1: CellLink, cell2: CellLink) {
 }
 
 pub fn unlink(cell1: CellLink, cell2: CellLink) {
-    let mut cell1_borrowed = cell1.borrow_mut();
-    match cell1_borrowed.links.iter().position(|v| match v.upgrade() {
-        Some(v) => *v.borrow() == *cell2.borrow(),
-        None => false,
-    }) {
-        Some(index) => {
-            cell1_borrowed.links.remove(index);
-        }
-        None => {}
-    };
-
-    let mut cell2_borrowed = cell2.borrow_mut();
-    match cell2_borrowed.links.iter().position(|v| match v.upgrade() {
-        Some(v) => *v.borrow() == *cell1.borrow(),
-        None => false,
-    }) {
-        Some(index) => {
-            cell2_borrowed.links.remove(index);
-        }
-        None => {}
-    };
+    let (key1, key2) = { (cell1.borrow().clone(), cell2.borrow().clone()) };
+
+    cell1.borrow_mut().links.retain(|v| match v.upgrade() {
+        Some(v) => *v.borrow() != key2,
+        None => true,
+    });
+
+    cell2.borrow_mut().links.retain(|v| match v.upgrade() {
+        Some(v) => *v.borrow() != key1,
+        None => true,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlink_drops_the_passage_both_ways() {
+        let (a, b, c) = (
+            Rc::new(RefCell::new(Cell::new(0, 0))),
+            Rc::new(RefCell::new(Cell::new(0, 1))),
+            Rc::new(RefCell::new(Cell::new(0, 2))),
+        );
+        link(a.clone(), b.clone());
+        link(b.clone(), c.clone());
+
+        unlink(b.clone(), a.clone());
+        assert!(a.borrow().links.is_empty());
+        assert!(b.borrow().linked(a.clone()).is_none());
+        assert!(b.borrow().linked(c.clone()).is_some());
+        assert!(c.borrow().linked(b.clone()).is_some());
+    }
 }
@@ -0,0 +1,195 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::random;
+use super::topology::*;
+
+/// The surface of a cube: six `dim` x `dim` faces, unfolded as
+///
+/// ```text
+///     [0]
+/// [1] [2] [3] [4]
+///     [5]
+/// ```
+///
+/// i.e. top, then left, front, right and back around the middle, then
+/// bottom. Faces are stacked in `grid`, so a cell's `row` is
+/// `face * dim + row on the face`.
+pub struct CubeGrid {
+    pub dim: usize,
+    pub grid: Vec<Vec<CellLink>>,
+    pub distance: Option<Distance>,
+}
+
+pub const FACES: usize = 6;
+
+impl CubeGrid {
+    pub fn new(dim: usize) -> Self {
+        let grid = (0..FACES * dim)
+            .map(|row| {
+                (0..dim)
+                    .map(|column| Rc::new(RefCell::new(Cell::new(row, column))))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        CubeGrid {
+            dim,
+            grid,
+            distance: None,
+        }
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.distance = Some(distance);
+    }
+
+    pub fn get_cell(&self, face: usize, row: usize, column: usize) -> Option<CellLink> {
+        if face >= FACES || row >= self.dim || column >= self.dim {
+            None
+        } else {
+            Some(self.grid[face * self.dim + row][column].clone())
+        }
+    }
+
+    /// The face of `cell` and its row on that face.
+    pub fn face_of(&self, cell: &Cell) -> (usize, usize) {
+        (cell.row / self.dim, cell.row % self.dim)
+    }
+
+    /// Finds the cell at `(row, column)` relative to `face`, where one
+    /// coordinate may be a step off the face and onto the adjoining one.
+    fn adjust(&self, face: usize, row: isize, column: isize) -> Option<CellLink> {
+        let last = self.dim - 1;
+        let (row_on, column_on) = (row as usize, column as usize);
+
+        let (face, row, column) = if row < 0 {
+            match face {
+                0 => (4, 0, last - column_on),
+                1 => (0, column_on, 0),
+                2 => (0, last, column_on),
+                3 => (0, last - column_on, last),
+                4 => (0, 0, last - column_on),
+                _ => (2, last, column_on),
+            }
+        } else if row_on > last {
+            match face {
+                0 => (2, 0, column_on),
+                1 => (5, last - column_on, 0),
+                2 => (5, 0, column_on),
+                3 => (5, column_on, last),
+                4 => (5, last, last - column_on),
+                _ => (4, last, last - column_on),
+            }
+        } else if column < 0 {
+            match face {
+                0 => (1, 0, row_on),
+                1 => (4, row_on, last),
+                2 => (1, row_on, last),
+                3 => (2, row_on, last),
+                4 => (3, row_on, last),
+                _ => (1, last, last - row_on),
+            }
+        } else if column_on > last {
+            match face {
+                0 => (3, 0, last - row_on),
+                1 => (2, row_on, 0),
+                2 => (3, row_on, 0),
+                3 => (4, row_on, 0),
+                4 => (1, row_on, 0),
+                _ => (3, last, row_on),
+            }
+        } else {
+            (face, row_on, column_on)
+        };
+
+        self.get_cell(face, row, column)
+    }
+
+    fn step(&self, cell: &Cell, row_offset: isize, column_offset: isize) -> Option<CellLink> {
+        if self.dim == 0 {
+            return None;
+        }
+
+        let (face, row) = self.face_of(cell);
+        self.adjust(
+            face,
+            row as isize + row_offset,
+            cell.column as isize + column_offset,
+        )
+    }
+
+    pub fn north(&self, cell: &Cell) -> Option<CellLink> {
+        self.step(cell, -1, 0)
+    }
+
+    pub fn south(&self, cell: &Cell) -> Option<CellLink> {
+        self.step(cell, 1, 0)
+    }
+
+    pub fn east(&self, cell: &Cell) -> Option<CellLink> {
+        self.step(cell, 0, 1)
+    }
+
+    pub fn west(&self, cell: &Cell) -> Option<CellLink> {
+        self.step(cell, 0, -1)
+    }
+}
+
+impl MazeGrid for CubeGrid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.clone()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        if self.size() == 0 {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        let face: usize = rng.gen_range(0..FACES);
+        let row: usize = rng.gen_range(0..self.dim);
+        let column: usize = rng.gen_range(0..self.dim);
+        self.get_cell(face, row, column)
+    }
+
+    fn size(&self) -> usize {
+        FACES * self.dim * self.dim
+    }
+}
+
+impl Topology for CubeGrid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        [
+            self.north(cell),
+            self.south(cell),
+            self.east(cell),
+            self.west(cell),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::tests::assert_symmetric;
+
+    #[test]
+    fn faces_join_symmetrically() {
+        for dim in 1..6 {
+            let mut grid = CubeGrid::new(dim);
+            grid.configure_cells();
+            assert_symmetric(&grid);
+            for cell in grid.each_cell() {
+                assert_eq!(cell.borrow().neighbors().len(), 4);
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+use super::cell::*;
+use super::grid::*;
+
+/// Unlinks every dead end from its only neighbor, `iterations` times over.
+/// Culled cells are left without links, i.e. solid rock.
+pub fn cull<G: MazeGrid>(grid: &mut G, iterations: usize) {
+    for _ in 0..iterations {
+        let dead_ends = grid.dead_ends();
+        if dead_ends.is_empty() {
+            break;
+        }
+
+        for cell in dead_ends {
+            let linked = cell.borrow().links.first().cloned().upgrade();
+            if let Some(linked) = linked {
+                unlink(cell.clone(), linked);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recursive_backtracker;
+    use crate::topology::Topology;
+
+    #[test]
+    fn one_pass_culls_every_dead_end() {
+        let mut grid = Grid::new(8, 8);
+        grid.configure_cells();
+        recursive_backtracker::on(&mut grid);
+        let dead_ends = grid.dead_ends();
+        let live = grid.size() - dead_ends.len();
+
+        cull(&mut grid, 1);
+        for cell in &dead_ends {
+            assert!(cell.borrow().links.is_empty());
+        }
+        let carved = grid.each_cell().iter().filter(|v| !v.borrow().links.is_empty()).count();
+        assert_eq!(carved, live);
+    }
+}
@@ -31,6 +31,19 @@ impl Distance {
         self.cells.keys().collect::<Vec<_>>()
     }
 
+    /// The distance to the farthest cell reached.
+    pub fn max(&self) -> usize {
+        self.cells.values().copied().max().unwrap_or(0)
+    }
+
+    /// The cells nearest first; for breadcrumbs from `path_to`, the path
+    /// from the root to the goal.
+    pub fn ordered(&self) -> Vec<&Cell> {
+        let mut cells = self.cells.iter().collect::<Vec<_>>();
+        cells.sort_by_key(|(cell, distance)| (**distance, cell.row, cell.column));
+        cells.into_iter().map(|(cell, _)| cell).collect::<Vec<_>>()
+    }
+
     pub fn distances(cell: CellLink) -> Self {
         let mut distances = Distance::new(cell.clone());
         let mut frontier: VecDeque<CellLink> = VecDeque::new();
@@ -39,23 +52,22 @@ impl Distance {
         while let Some(cell) = frontier.pop_front() {
             let cell_borrowed = cell.borrow();
             for linked in &cell_borrowed.links {
-                match linked.upgrade().clone() {
-                    Some(linked) => {
-                        if distances.get(linked.clone()).is_none() {
-                            distances.set(
-                                linked.clone(),
-                                distances.get(cell.clone()).map_or(0, |v| v) + 1,
-                            );
-                            frontier.push_back(linked);
-                        }
+                if let Some(linked) = linked.upgrade() {
+                    if distances.get(linked.clone()).is_none() {
+                        distances.set(
+                            linked.clone(),
+                            distances.get(cell.clone()).map_or(0, |v| v) + 1,
+                        );
+                        frontier.push_back(linked);
                     }
-                    None => (),
                 }
             }
         }
         distances
     }
 
+    /// The cells on the way from the root to `goal`; just the root when
+    /// `goal` was never reached.
     pub fn path_to(&self, goal: CellLink) -> Self {
         let mut current = goal;
 
@@ -65,7 +77,7 @@ impl Distance {
                 breadcomb.set(current.clone(), distance);
                 distance
             }
-            None => 0,
+            None => return breadcomb,
         };
 
         loop {
@@ -74,19 +86,28 @@ impl Distance {
                 break;
             }
             let links = { current.borrow().clone().links };
+            let mut stepped = false;
             for neighbor in links {
                 let neighbor = match neighbor.upgrade().clone() {
                     Some(neighbor) => neighbor,
                     None => continue,
                 };
-                let distance_neighbor = self.get(neighbor.clone()).unwrap_or(0);
+                let distance_neighbor = match self.get(neighbor.clone()) {
+                    Some(distance_neighbor) => distance_neighbor,
+                    None => continue,
+                };
                 if distance_neighbor < distance {
                     breadcomb.set(neighbor.clone(), distance_neighbor);
                     distance = distance_neighbor;
                     current = neighbor;
+                    stepped = true;
                     break;
                 }
             }
+            // Only if the maze changed since the distances were taken.
+            if !stepped {
+                break;
+            }
         }
 
         breadcomb
@@ -107,3 +128,22 @@ pub fn distance_str(input: Option<usize>) -> String {
         None => String::from(" "),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::*;
+    use crate::topology::Topology;
+
+    #[test]
+    fn path_to_an_unreached_goal_is_just_the_root() {
+        let mut grid = Grid::new(2, 3);
+        grid.configure_cells();
+        let (a, b, c) = (grid.grid[0][0].clone(), grid.grid[0][1].clone(), grid.grid[1][2].clone());
+        grid.link(a.clone(), b.clone());
+
+        let path = Distance::distances(a.clone()).path_to(c);
+        assert_eq!(path.cells.len(), 1);
+        assert_eq!(path.get(a), Some(0));
+    }
+}
@@ -0,0 +1,323 @@
+#![allow(dead_code)]
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use super::grid::*;
+use super::hex_grid::*;
+use super::polar_grid::*;
+use super::voronoi_grid::Point;
+
+/// One piece of a wall or a cell outline.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Stroke {
+    Line(Point, Point),
+    /// Part of a circle from angle `start` to `end`, in radians measured
+    /// clockwise on screen from the positive x axis.
+    Arc {
+        center: Point,
+        radius: f64,
+        start: f64,
+        end: f64,
+    },
+}
+
+fn on_circle(center: Point, radius: f64, angle: f64) -> Point {
+    (
+        center.0 + radius * angle.cos(),
+        center.1 + radius * angle.sin(),
+    )
+}
+
+impl Stroke {
+    pub fn from(&self) -> Point {
+        match self {
+            Stroke::Line(from, _) => *from,
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                ..
+            } => on_circle(*center, *radius, *start),
+        }
+    }
+
+    pub fn to(&self) -> Point {
+        match self {
+            Stroke::Line(_, to) => *to,
+            Stroke::Arc {
+                center,
+                radius,
+                end,
+                ..
+            } => on_circle(*center, *radius, *end),
+        }
+    }
+
+    /// The stroke as a polyline, arcs split into steps of a few degrees.
+    pub fn points(&self) -> Vec<Point> {
+        match self {
+            Stroke::Line(from, to) => vec![*from, *to],
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => {
+                let steps = ((end - start).abs() / (PI / 60.0)).ceil().max(1.0) as usize;
+                (0..=steps)
+                    .map(|v| on_circle(*center, *radius, start + (end - start) * v as f64 / steps as f64))
+                    .collect::<Vec<_>>()
+            }
+        }
+    }
+}
+
+/// Where everything of a non-square grid goes on the page.
+pub struct Layout {
+    pub width: f64,
+    pub height: f64,
+    pub walls: Vec<Stroke>,
+    /// The outline of each cell, in order around it, for filling.
+    pub cells: Vec<((usize, usize), Vec<Stroke>)>,
+    pub centers: HashMap<(usize, usize), Point>,
+}
+
+/// Lays out a polar grid as rings `size` deep around the middle, with
+/// `margin` around the outside.
+pub fn polar(grid: &PolarGrid, size: f64, margin: f64) -> Layout {
+    let extent = grid.rows as f64 * size;
+    let center = (extent + margin, extent + margin);
+    let mut layout = Layout {
+        width: 2.0 * (extent + margin),
+        height: 2.0 * (extent + margin),
+        walls: Vec::new(),
+        cells: Vec::new(),
+        centers: HashMap::new(),
+    };
+    let circle = |radius: f64| {
+        vec![
+            Stroke::Arc {
+                center,
+                radius,
+                start: 0.0,
+                end: PI,
+            },
+            Stroke::Arc {
+                center,
+                radius,
+                start: PI,
+                end: 2.0 * PI,
+            },
+        ]
+    };
+
+    for cell in grid.each_cell() {
+        let cell = cell.borrow();
+        let (row, column) = (cell.row, cell.column);
+        if row == 0 {
+            layout.cells.push(((row, column), circle(size)));
+            layout.centers.insert((row, column), center);
+            continue;
+        }
+
+        let theta = 2.0 * PI / grid.grid[row].len() as f64;
+        let (inner, outer) = (row as f64 * size, (row + 1) as f64 * size);
+        let (ccw, cw) = (column as f64 * theta, (column + 1) as f64 * theta);
+
+        if cell.walled(grid.inward(&cell)) {
+            layout.walls.push(Stroke::Arc {
+                center,
+                radius: inner,
+                start: ccw,
+                end: cw,
+            });
+        }
+        if cell.walled(grid.cw(&cell)) {
+            layout.walls.push(Stroke::Line(
+                on_circle(center, inner, cw),
+                on_circle(center, outer, cw),
+            ));
+        }
+
+        let outline = vec![
+            Stroke::Line(on_circle(center, inner, ccw), on_circle(center, outer, ccw)),
+            Stroke::Arc {
+                center,
+                radius: outer,
+                start: ccw,
+                end: cw,
+            },
+            Stroke::Line(on_circle(center, outer, cw), on_circle(center, inner, cw)),
+            Stroke::Arc {
+                center,
+                radius: inner,
+                start: cw,
+                end: ccw,
+            },
+        ];
+        layout.cells.push(((row, column), outline));
+        layout.centers.insert(
+            (row, column),
+            on_circle(center, (row as f64 + 0.5) * size, (column as f64 + 0.5) * theta),
+        );
+    }
+
+    layout.walls.extend(circle(extent));
+    layout
+}
+
+/// Lays out a hex grid of hexagons `size` across from corner to corner,
+/// with `margin` around the outside.
+pub fn hex(grid: &HexGrid, size: f64, margin: f64) -> Layout {
+    let radius = size / 2.0;
+    let (a, b) = (radius / 2.0, radius * 3f64.sqrt() / 2.0);
+    let mut layout = Layout {
+        width: 3.0 * a * grid.columns as f64 + a + 2.0 * margin,
+        height: 2.0 * b * grid.rows as f64 + b + 2.0 * margin,
+        walls: Vec::new(),
+        cells: Vec::new(),
+        centers: HashMap::new(),
+    };
+
+    for cell in grid.each_cell() {
+        let cell = cell.borrow();
+        let (row, column) = (cell.row, cell.column);
+        let x = margin + radius + 3.0 * a * column as f64;
+        let offset = if column.is_multiple_of(2) { 0.0 } else { b };
+        let y = margin + b + 2.0 * b * row as f64 + offset;
+
+        let (far_west, near_west, near_east, far_east) = (x - radius, x - a, x + a, x + radius);
+        let (north, middle, south) = (y - b, y, y + b);
+        let corners = [
+            (far_west, middle),
+            (near_west, north),
+            (near_east, north),
+            (far_east, middle),
+            (near_east, south),
+            (near_west, south),
+        ];
+
+        // Each wall is drawn once: by the cell on its west or north side,
+        // or by whichever cell is there when the other is off the grid.
+        let sides = [
+            (grid.northwest(&cell), true),
+            (grid.north(&cell), true),
+            (grid.northeast(&cell), false),
+            (grid.southeast(&cell), false),
+            (grid.south(&cell), false),
+            (grid.southwest(&cell), true),
+        ];
+        for (index, (neighbor, edge_only)) in sides.into_iter().enumerate() {
+            let drawn = if edge_only {
+                neighbor.is_none()
+            } else {
+                cell.walled(neighbor)
+            };
+            if drawn {
+                layout
+                    .walls
+                    .push(Stroke::Line(corners[index], corners[(index + 1) % 6]));
+            }
+        }
+
+        let outline = (0..6)
+            .map(|v| Stroke::Line(corners[v], corners[(v + 1) % 6]))
+            .collect::<Vec<_>>();
+        layout.cells.push(((row, column), outline));
+        layout.centers.insert((row, column), (x, y));
+    }
+
+    layout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recursive_backtracker;
+    use crate::topology::Topology;
+
+    fn key((x, y): Point) -> (i64, i64) {
+        ((x * 1000.0).round() as i64, (y * 1000.0).round() as i64)
+    }
+
+    /// A stroke by its ends, whichever way round it was drawn, and its
+    /// middle, which tells apart arcs between the same ends.
+    fn ends(stroke: &Stroke) -> ((i64, i64), (i64, i64), (i64, i64)) {
+        let (from, to) = (key(stroke.from()), key(stroke.to()));
+        let points = stroke.points();
+        let middle = if points.len() % 2 == 1 {
+            key(points[points.len() / 2])
+        } else {
+            let (a, b) = (points[points.len() / 2 - 1], points[points.len() / 2]);
+            key(((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0))
+        };
+        (from.min(to), from.max(to), middle)
+    }
+
+    fn links<G: MazeGrid>(grid: &G) -> usize {
+        grid.each_cell().iter().map(|v| v.borrow().links.len()).sum::<usize>() / 2
+    }
+
+    /// Every cell has a center and a closed outline.
+    fn assert_cells(layout: &Layout, size: usize) {
+        assert_eq!(layout.cells.len(), size);
+        assert_eq!(layout.centers.len(), size);
+        for (address, outline) in &layout.cells {
+            assert!(layout.centers.contains_key(address));
+            for (index, stroke) in outline.iter().enumerate() {
+                let next = &outline[(index + 1) % outline.len()];
+                assert_eq!(key(stroke.to()), key(next.from()), "{:?}", address);
+            }
+        }
+    }
+
+    fn assert_drawn_once(layout: &Layout) {
+        let mut walls = layout.walls.iter().map(ends).collect::<Vec<_>>();
+        let count = walls.len();
+        walls.sort();
+        walls.dedup();
+        assert_eq!(walls.len(), count, "a wall is drawn twice");
+    }
+
+    #[test]
+    fn hex_walls_are_drawn_once() {
+        let mut grid = HexGrid::new(4, 5);
+        grid.configure_cells();
+        let mut sides = hex(&grid, 20.0, 5.0)
+            .cells
+            .iter()
+            .flat_map(|(_, outline)| outline.iter().map(ends).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        sides.sort();
+        sides.dedup();
+
+        for carve in [false, true] {
+            if carve {
+                recursive_backtracker::on(&mut grid);
+            }
+            let layout = hex(&grid, 20.0, 5.0);
+            assert_cells(&layout, 20);
+            assert_drawn_once(&layout);
+            // Every side of every hexagon, less the ones carved through.
+            assert_eq!(layout.walls.len(), sides.len() - links(&grid));
+        }
+    }
+
+    #[test]
+    fn polar_walls_are_drawn_once() {
+        let mut grid = PolarGrid::new(5);
+        grid.configure_cells();
+        for carve in [false, true] {
+            if carve {
+                recursive_backtracker::on(&mut grid);
+            }
+            let layout = polar(&grid, 10.0, 2.0);
+            let size = grid.size();
+            assert_cells(&layout, size);
+            assert_drawn_once(&layout);
+            // An inner and a clockwise wall for each cell off the middle,
+            // and the outer circle in two halves.
+            assert_eq!(layout.walls.len(), 2 * (size - 1) + 2 - links(&grid));
+        }
+    }
+}
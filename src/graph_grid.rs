@@ -0,0 +1,273 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::io;
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::random;
+use super::topology::*;
+use super::voronoi_grid::Point;
+
+/// A maze substrate built from any undirected graph, such as a floor plan
+/// or a subway map: each node is a cell and each edge makes two cells
+/// neighbors.
+///
+/// Cells are addressed by breadth-first rings from the first node, so every
+/// cell of its component but the first has a neighbor in the row before
+/// it. Each further component starts new rings of its own; generators can
+/// only link cells within a component.
+pub struct GraphGrid {
+    pub names: Vec<String>,
+    pub positions: Option<Vec<Point>>,
+    pub grid: Vec<Vec<CellLink>>,
+    pub nodes: Vec<Vec<usize>>,
+    pub adjacency: Vec<Vec<Vec<(usize, usize)>>>,
+    pub distance: Option<Distance>,
+}
+
+/// Splits the nodes of `graph` into rings, each one edge further from
+/// `root` than the last. Nodes out of reach of `root` follow in rings
+/// around the first of them, and so on until every node is placed.
+pub fn rings(graph: &[Vec<usize>], root: usize) -> Vec<Vec<usize>> {
+    let mut visited = vec![false; graph.len()];
+    let mut rings: Vec<Vec<usize>> = Vec::new();
+
+    let roots = std::iter::once(root).chain(0..graph.len());
+    for root in roots.filter(|v| *v < graph.len()) {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+
+        let mut frontier = vec![root];
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for index in &frontier {
+                for neighbor in &graph[*index] {
+                    if !visited[*neighbor] {
+                        visited[*neighbor] = true;
+                        next.push(*neighbor);
+                    }
+                }
+            }
+            rings.push(frontier);
+            frontier = next;
+        }
+    }
+
+    rings
+}
+
+impl GraphGrid {
+    /// Repeated edges and edges from a node to itself are ignored; an edge
+    /// naming a node past the end of `nodes` is an error.
+    pub fn new<S: Into<String>>(nodes: Vec<S>, edges: &[(usize, usize)]) -> io::Result<Self> {
+        let names = nodes.into_iter().map(|v| v.into()).collect::<Vec<_>>();
+        let count = names.len();
+
+        let mut graph = vec![Vec::new(); count];
+        for (a, b) in edges {
+            if *a >= count || *b >= count {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("edge ({}, {}) refers to a missing node", a, b),
+                ));
+            }
+            if a != b && !graph[*a].contains(b) {
+                graph[*a].push(*b);
+                graph[*b].push(*a);
+            }
+        }
+
+        let nodes = rings(&graph, 0);
+        let mut address: Vec<(usize, usize)> = vec![(0, 0); count];
+        for (row, ring) in nodes.iter().enumerate() {
+            for (column, index) in ring.iter().enumerate() {
+                address[*index] = (row, column);
+            }
+        }
+
+        let adjacency = nodes
+            .iter()
+            .map(|ring| {
+                ring.iter()
+                    .map(|index| graph[*index].iter().map(|v| address[*v]).collect())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let grid = nodes
+            .iter()
+            .enumerate()
+            .map(|(row, ring)| {
+                (0..ring.len())
+                    .map(|column| Rc::new(RefCell::new(Cell::new(row, column))))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        Ok(GraphGrid {
+            names,
+            positions: None,
+            grid,
+            nodes,
+            adjacency,
+            distance: None,
+        })
+    }
+
+    /// Like `new`, with a position for every node for drawing the maze; an
+    /// error unless there are as many positions as nodes.
+    pub fn with_positions<S: Into<String>>(
+        nodes: Vec<S>,
+        positions: Vec<Point>,
+        edges: &[(usize, usize)],
+    ) -> io::Result<Self> {
+        let mut grid = GraphGrid::new(nodes, edges)?;
+        if positions.len() != grid.names.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} positions for {} nodes",
+                    positions.len(),
+                    grid.names.len()
+                ),
+            ));
+        }
+        grid.positions = Some(positions);
+        Ok(grid)
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.distance = Some(distance);
+    }
+
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<CellLink> {
+        self.grid.get(row)?.get(column).cloned()
+    }
+
+    /// The cell for the node at `index` in the original node list.
+    pub fn node(&self, index: usize) -> Option<CellLink> {
+        self.nodes.iter().enumerate().find_map(|(row, ring)| {
+            let column = ring.iter().position(|v| *v == index)?;
+            self.get_cell(row, column)
+        })
+    }
+
+    /// The cell for the first node called `name`.
+    pub fn named(&self, name: &str) -> Option<CellLink> {
+        self.node(self.names.iter().position(|v| v == name)?)
+    }
+
+    pub fn index(&self, cell: &Cell) -> usize {
+        self.nodes[cell.row][cell.column]
+    }
+
+    pub fn name(&self, cell: &Cell) -> &str {
+        &self.names[self.index(cell)]
+    }
+
+    pub fn position(&self, cell: &Cell) -> Option<Point> {
+        Some(self.positions.as_ref()?[self.index(cell)])
+    }
+}
+
+impl MazeGrid for GraphGrid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.clone()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        let cells = self.each_cell();
+        if cells.is_empty() {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        Some(cells[rng.gen_range(0..cells.len())].clone())
+    }
+
+    fn size(&self) -> usize {
+        self.names.len()
+    }
+}
+
+impl Topology for GraphGrid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        self.adjacency[cell.row][cell.column]
+            .iter()
+            .filter_map(|(row, column)| self.get_cell(*row, *column))
+            .collect::<Vec<_>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recursive_backtracker;
+
+    fn address(cell: Option<CellLink>) -> Option<(usize, usize)> {
+        cell.map(|v| (v.borrow().row, v.borrow().column))
+    }
+
+    #[test]
+    fn repeated_edges_and_loops_are_ignored() {
+        let mut grid =
+            GraphGrid::new(vec!["a", "b", "c"], &[(0, 1), (1, 0), (0, 1), (2, 2), (1, 2)]).unwrap();
+        grid.configure_cells();
+        let degrees = (0..3)
+            .map(|v| grid.node(v).unwrap().borrow().neighbors().len())
+            .collect::<Vec<_>>();
+        assert_eq!(degrees, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn components_start_rings_of_their_own() {
+        // Two triangles with no edge between them.
+        let edges = [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)];
+        let mut grid = GraphGrid::new(vec!["a", "b", "c", "d", "e", "f"], &edges).unwrap();
+        grid.configure_cells();
+        assert_eq!(grid.nodes, vec![vec![0], vec![1, 2], vec![3], vec![4, 5]]);
+
+        // The walk spans the component it starts in and never leaves it.
+        recursive_backtracker::on(&mut grid);
+        let carved = grid
+            .each_cell()
+            .into_iter()
+            .filter(|v| !v.borrow().links.is_empty())
+            .collect::<Vec<_>>();
+        assert_eq!(carved.len(), 3);
+        let reached = Distance::distances(carved[0].clone()).cells.len();
+        assert_eq!(reached, 3);
+    }
+
+    #[test]
+    fn cells_are_found_by_name_and_index() {
+        let grid = GraphGrid::new(vec!["hub", "north", "south", "north"], &[(0, 1), (0, 2), (0, 3)])
+            .unwrap();
+        assert_eq!(address(grid.named("hub")), Some((0, 0)));
+        assert_eq!(address(grid.named("south")), Some((1, 1)));
+        // The first of two nodes with the same name.
+        assert_eq!(address(grid.named("north")), address(grid.node(1)));
+        assert!(grid.named("east").is_none());
+        assert!(grid.node(4).is_none());
+
+        let cell = grid.node(3).unwrap();
+        assert_eq!(grid.index(&cell.borrow()), 3);
+        assert_eq!(grid.name(&cell.borrow()), "north");
+    }
+
+    #[test]
+    fn bad_input_is_an_error() {
+        let error = GraphGrid::new(vec!["a", "b"], &[(0, 2)]).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        let positions = vec![(0.0, 0.0)];
+        assert!(GraphGrid::with_positions(vec!["a", "b"], positions, &[(0, 1)]).is_err());
+        let positions = vec![(0.0, 0.0), (1.0, 0.0)];
+        let grid = GraphGrid::with_positions(vec!["a", "b"], positions, &[(0, 1)]).unwrap();
+        assert_eq!(grid.position(&grid.node(1).unwrap().borrow()), Some((1.0, 0.0)));
+    }
+}
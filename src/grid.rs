@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+use rand::seq::SliceRandom;
 use rand::Rng;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
@@ -6,12 +7,133 @@ use std::{cell::RefCell, rc::Rc};
 
 use super::cell::*;
 use super::distance::*;
+use super::mask::*;
+use super::random;
+use super::topology::*;
+
+/// What the generators need from a grid, whatever its shape.
+pub trait MazeGrid {
+    /// Rows of enabled cells, skipping anything a mask turns off.
+    fn each_row(&self) -> Vec<Vec<CellLink>>;
+
+    fn random_cell(&self) -> Option<CellLink>;
+
+    fn size(&self) -> usize;
+
+    /// The cells a generator may carve into from `cell`. Usually the
+    /// neighbors stored by the topology, but a grid may add more.
+    fn neighbors(&self, cell: &CellLink) -> Vec<CellLink> {
+        cell.borrow().neighbors()
+    }
+
+    /// Carves a passage between `cell1` and one of its `neighbors`.
+    fn link(&mut self, cell1: CellLink, cell2: CellLink) {
+        link(cell1, cell2);
+    }
+
+    /// Binary tree and sidewinder carve only through `run_exit` and
+    /// `run_next`, so between them the two must lead every cell but one
+    /// back to that root without going round in circles. Neither is a
+    /// compass direction; a grid may answer with any of its neighbors.
+    ///
+    /// `run_exit` is the neighbor a run closes out through. By default it
+    /// is a neighbor in the nearest earlier row, preferably in the same
+    /// column, i.e. north on a square grid.
+    fn run_exit(&self, cell: &CellLink) -> Option<CellLink> {
+        let cell = cell.borrow();
+        let above = cell
+            .neighbors()
+            .into_iter()
+            .filter(|v| v.borrow().row < cell.row)
+            .collect::<Vec<_>>();
+        let nearest = above.iter().map(|v| v.borrow().row).max()?;
+        above
+            .iter()
+            .filter(|v| v.borrow().row == nearest)
+            .find(|v| v.borrow().column == cell.column)
+            .or_else(|| above.iter().find(|v| v.borrow().row == nearest))
+            .cloned()
+    }
+
+    /// The neighbor that carries a run on. By default the next cell along
+    /// the same row, i.e. east on a square grid, and `None` at the end of
+    /// the row even where the row wraps around.
+    fn run_next(&self, cell: &CellLink) -> Option<CellLink> {
+        let cell = cell.borrow();
+        cell.neighbors().into_iter().find(|v| {
+            let v = v.borrow();
+            v.row == cell.row && v.column == cell.column + 1
+        })
+    }
+
+    fn each_cell(&self) -> Vec<CellLink> {
+        self.each_row().into_iter().flatten().collect::<Vec<_>>()
+    }
+
+    fn dead_ends(&self) -> Vec<CellLink> {
+        self.each_cell()
+            .into_iter()
+            .filter(|v| v.borrow().links.len() == 1)
+            .collect::<Vec<_>>()
+    }
+}
+
+/// Carves one passage at a time from the cells reachable from the first
+/// cell into a neighbor that is not, until every cell can be reached.
+/// Generators that only look north and east strand cells a mask cuts off
+/// that way; this joins them back on without closing any loop.
+pub fn join_regions<G: MazeGrid>(grid: &mut G) {
+    let mut rng = random::rng();
+    let cells = grid.each_cell();
+    let Some(first) = cells.first() else {
+        return;
+    };
+
+    loop {
+        let reached = Distance::distances(first.clone());
+        if reached.cells.len() == cells.len() {
+            return;
+        }
+
+        let edges = cells
+            .iter()
+            .filter(|v| reached.get((*v).clone()).is_some())
+            .flat_map(|v| {
+                grid.neighbors(v)
+                    .into_iter()
+                    .filter(|w| reached.get(w.clone()).is_none())
+                    .map(|w| (v.clone(), w))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        match edges.choose(&mut rng) {
+            Some((cell1, cell2)) => grid.link(cell1.clone(), cell2.clone()),
+            // The rest of the grid is walled off by the mask itself.
+            None => return,
+        }
+    }
+}
+
+/// How the edges of a square grid join up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Wrap {
+    /// No wrapping; the outer edges are walls.
+    Flat,
+    /// The east edge joins the west edge.
+    Cylinder,
+    /// Both the east/west and the north/south edges join.
+    Torus,
+    /// The east edge joins the west edge upside down.
+    Moebius,
+}
 
 pub struct Grid {
     pub rows: usize,
     pub columns: usize,
     pub grid: Vec<Vec<CellLink>>,
     pub distance: Option<Distance>,
+    pub mask: Option<Mask>,
+    pub wrap: Wrap,
 }
 
 impl Grid {
@@ -30,153 +152,375 @@ impl Grid {
             rows,
             columns,
             grid,
-            distance: None
+            distance: None,
+            mask: None,
+            wrap: Wrap::Flat,
         }
     }
 
-    pub fn configure_cells(&mut self) {
-        for row in &self.grid {
-            for cell in row {
-                let mut cell = cell.borrow_mut();
-                let row = cell.row;
-                let column = cell.column;
+    pub fn wrapped(rows: usize, columns: usize, wrap: Wrap) -> Self {
+        let mut grid = Grid::new(rows, columns);
+        grid.wrap = wrap;
+        grid
+    }
 
-                if row >= 1 {
-                    cell.north = self.get_cell(row - 1, column).map(|v| v.downgrade());
-                }
+    pub fn cylinder(rows: usize, columns: usize) -> Self {
+        Grid::wrapped(rows, columns, Wrap::Cylinder)
+    }
 
-                if row < self.rows - 1 {
-                    cell.south = self.get_cell(row + 1, column).map(|v| v.downgrade());
-                }
+    pub fn torus(rows: usize, columns: usize) -> Self {
+        Grid::wrapped(rows, columns, Wrap::Torus)
+    }
 
-                if column >= 1 {
-                    cell.west = self.get_cell(row, column - 1).map(|v| v.downgrade());
-                }
+    pub fn moebius(rows: usize, columns: usize) -> Self {
+        Grid::wrapped(rows, columns, Wrap::Moebius)
+    }
 
-                if column < self.columns - 1 {
-                    cell.east = self.get_cell(row, column + 1).map(|v| v.downgrade());
-                }
-            }
-        }
+    /// Creates a grid shaped by `mask`; disabled cells are never configured,
+    /// returned by `get_cell` or handed to the generators.
+    pub fn masked(mask: Mask) -> Self {
+        let mut grid = Grid::new(mask.rows, mask.columns);
+        grid.mask = Some(mask);
+        grid
     }
 
     pub fn set_distance(&mut self, distance: Distance) {
         self.distance = Some(distance);
     }
 
+    pub fn enabled(&self, row: usize, column: usize) -> bool {
+        match &self.mask {
+            Some(mask) => mask.get(row, column),
+            None => row < self.rows && column < self.columns,
+        }
+    }
+
     pub fn get_cell(&self, row: usize, column: usize) -> Option<CellLink> {
-        if row >= self.rows || column >= self.columns {
+        if row >= self.rows || column >= self.columns || !self.enabled(row, column) {
             None
         } else {
-            Some(self.grid[row as usize][column as usize].clone())
+            Some(self.grid[row][column].clone())
+        }
+    }
+
+    pub fn wraps_horizontally(&self) -> bool {
+        self.wrap != Wrap::Flat
+    }
+
+    pub fn wraps_vertically(&self) -> bool {
+        self.wrap == Wrap::Torus
+    }
+
+    pub fn north(&self, cell: &Cell) -> Option<CellLink> {
+        if cell.row == 0 && self.wraps_vertically() {
+            self.get_cell(self.rows - 1, cell.column)
+        } else {
+            cell.row
+                .checked_sub(1)
+                .and_then(|row| self.get_cell(row, cell.column))
+        }
+    }
+
+    pub fn south(&self, cell: &Cell) -> Option<CellLink> {
+        if cell.row + 1 == self.rows && self.wraps_vertically() {
+            self.get_cell(0, cell.column)
+        } else {
+            self.get_cell(cell.row + 1, cell.column)
+        }
+    }
+
+    pub fn east(&self, cell: &Cell) -> Option<CellLink> {
+        if cell.column + 1 == self.columns && self.wraps_horizontally() {
+            self.get_cell(self.seam_row(cell.row), 0)
+        } else {
+            self.get_cell(cell.row, cell.column + 1)
+        }
+    }
+
+    pub fn west(&self, cell: &Cell) -> Option<CellLink> {
+        if cell.column == 0 && self.wraps_horizontally() {
+            self.get_cell(self.seam_row(cell.row), self.columns - 1)
+        } else {
+            cell.column
+                .checked_sub(1)
+                .and_then(|column| self.get_cell(cell.row, column))
+        }
+    }
+
+    /// The row met on the other side of the east/west seam.
+    fn seam_row(&self, row: usize) -> usize {
+        if self.wrap == Wrap::Moebius {
+            self.rows - 1 - row
+        } else {
+            row
+        }
+    }
+
+    /// Every wall of the grid by line: `horizontal[r][c]` is the wall above
+    /// cell `(r, c)` and `vertical[c][r]` the wall left of it, with an extra
+    /// last line for the bottom and right edges. Seams that were carved
+    /// through have no outer wall.
+    pub fn walls(&self) -> (Vec<Vec<bool>>, Vec<Vec<bool>>) {
+        let mut horizontal = vec![vec![false; self.columns]; self.rows + 1];
+        let mut vertical = vec![vec![false; self.rows]; self.columns + 1];
+        for cell in self.each_cell() {
+            let cell = cell.borrow();
+            let (row, column) = (cell.row, cell.column);
+            horizontal[row][column] |= cell.walled(self.north(&cell));
+            horizontal[row + 1][column] |= cell.walled(self.south(&cell));
+            vertical[column][row] |= cell.walled(self.west(&cell));
+            vertical[column + 1][row] |= cell.walled(self.east(&cell));
         }
+        (horizontal, vertical)
     }
 
     pub fn print_cells(&self, row: usize, column: usize) {
-        match self.get_cell(row, column) {
-            Some(cell) => {
-                let cell = cell.borrow();
-                println!("row: {}, column: {}", cell.row, cell.column);
-
-                let north = &cell.north();
-                match north {
-                    Some(north) => {
-                        let north = north.borrow();
-                        println!("north: row: {}, column: {}", north.row, north.column);
-                    }
-                    None => (),
-                }
+        if let Some(cell) = self.get_cell(row, column) {
+            let cell = cell.borrow();
+            println!("row: {}, column: {}", cell.row, cell.column);
 
-                let south = &cell.south();
-                match south {
-                    Some(south) => {
-                        let south = south.borrow();
-                        println!("south: row: {}, column: {}", south.row, south.column);
-                    }
-                    None => (),
-                }
+            if let Some(north) = &self.north(&cell) {
+                let north = north.borrow();
+                println!("north: row: {}, column: {}", north.row, north.column);
+            }
 
-                let east = &cell.east();
-                match east {
-                    Some(east) => {
-                        let east = east.borrow();
-                        println!("east: row: {}, column: {}", east.row, east.column);
-                    }
-                    None => (),
-                }
+            if let Some(south) = &self.south(&cell) {
+                let south = south.borrow();
+                println!("south: row: {}, column: {}", south.row, south.column);
+            }
 
-                let west = &cell.west();
-                match west {
-                    Some(west) => {
-                        let west = west.borrow();
-                        println!("west: row: {}, column: {}", west.row, west.column);
-                    }
-                    None => (),
+            if let Some(east) = &self.east(&cell) {
+                let east = east.borrow();
+                println!("east: row: {}, column: {}", east.row, east.column);
+            }
+
+            if let Some(west) = &self.west(&cell) {
+                let west = west.borrow();
+                println!("west: row: {}, column: {}", west.row, west.column);
+            }
+        }
+    }
+}
+
+/// Splits a path of `(row, column)` cells where it jumps across a wrapping
+/// seam, so that it is drawn as separate strokes rather than one crossing
+/// the whole maze.
+pub fn split_at_seams(path: &[(usize, usize)]) -> Vec<&[(usize, usize)]> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for index in 1..=path.len() {
+        let adjacent = path.get(index).is_some_and(|(row, column)| {
+            let (previous_row, previous_column) = path[index - 1];
+            row.abs_diff(previous_row) + column.abs_diff(previous_column) == 1
+        });
+        if !adjacent {
+            runs.push(&path[start..index]);
+            start = index;
+        }
+    }
+    runs
+}
+
+impl MazeGrid for Grid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .filter(|v| {
+                        let cell = v.borrow();
+                        self.enabled(cell.row, cell.column)
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        match &self.mask {
+            Some(mask) => {
+                let (row, column) = mask.random_location()?;
+                self.get_cell(row, column)
+            }
+            None => {
+                if self.size() == 0 {
+                    return None;
                 }
+
+                let mut rng = random::rng();
+                let row: usize = rng.gen_range(0..self.rows);
+                let column: usize = rng.gen_range(0..self.columns);
+                self.get_cell(row, column)
             }
-            None => (),
         }
     }
 
-    pub fn random_cell(&self) -> Option<CellLink> {
-        let mut rng = rand::thread_rng();
-        let row: usize = rng.gen_range(0..self.rows);
-        let column: usize = rng.gen_range(0..self.rows);
-        self.get_cell(row, column)
+    fn size(&self) -> usize {
+        match &self.mask {
+            Some(mask) => mask.count(),
+            None => self.rows * self.columns,
+        }
     }
+}
 
-    pub fn size(&self) -> usize {
-        self.rows * self.columns
+impl Topology for Grid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        [
+            self.north(cell),
+            self.south(cell),
+            self.east(cell),
+            self.west(cell),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
     }
 }
 
 impl Debug for Grid {
+    /// The grid's shape; the maze itself is drawn by a `Renderer`.
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        for _ in 0..self.columns {
-            write!(f, "+---")?;
-        }
-        writeln!(f, "+")?;
-
-        for row in &self.grid {
-            let mut top_str = String::from("|");
-            let mut bottom_str = String::from("+");
-            for cell in row {
-                let cell_borrowed = &cell.borrow();
-                top_str.push(' ');
-                let distance_str = match &self.distance {
-                    Some(distance) => distance_str(distance.get(cell.clone())),
-                    None => String::from(" "),
-                };
-                top_str.push_str(distance_str.as_str());
-                top_str.push(' ');
-                match cell_borrowed.east() {
-                    Some(east) => {
-                        if cell_borrowed.linked(east).is_some() {
-                            top_str.push(' ');
-                        } else {
-                            top_str.push('|');
-                        }
-                    }
-                    None => top_str.push('|'),
-                }
+        f.debug_struct("Grid")
+            .field("rows", &self.rows)
+            .field("columns", &self.columns)
+            .field("wrap", &self.wrap)
+            .field("mask", &self.mask)
+            .field("size", &self.size())
+            .field("has_distance", &self.distance.is_some())
+            .finish()
+    }
+}
 
-                match cell_borrowed.south() {
-                    Some(south) => {
-                        if cell_borrowed.linked(south).is_some() {
-                            bottom_str.push_str("   ");
-                        } else {
-                            bottom_str.push_str("---");
-                        }
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::distance::Distance;
+
+    /// Every neighbor of a cell has the cell as a neighbor in turn.
+    pub fn assert_symmetric<G: MazeGrid>(grid: &G) {
+        for cell in grid.each_cell() {
+            let cell = cell.borrow();
+            for neighbor in cell.neighbors() {
+                let neighbor = neighbor.borrow();
+                assert!(
+                    neighbor.neighbors().iter().any(|v| *v.borrow() == *cell),
+                    "{:?} borders {:?} but not the other way",
+                    (cell.row, cell.column),
+                    (neighbor.row, neighbor.column)
+                );
+            }
+        }
+    }
+
+    /// The passages join every cell, with no loops.
+    pub fn assert_spanning_tree<G: MazeGrid>(grid: &G, name: &str) {
+        let cells = grid.each_cell();
+        let links = cells.iter().map(|v| v.borrow().links.len()).sum::<usize>() / 2;
+        let reached = Distance::distances(cells[0].clone()).cells.len();
+        assert_eq!(reached, cells.len(), "{}: not every cell is reached", name);
+        assert_eq!(links, cells.len() - 1, "{}: the passages form loops", name);
+    }
+
+    /// Runs a generator's `on` over one grid of every shape.
+    macro_rules! assert_spans_every_grid {
+        ($on:path) => {{
+            use crate::grid::tests::assert_spanning_tree;
+            use crate::topology::Topology;
+
+            macro_rules! check {
+                ($name:expr, $grid:expr) => {{
+                    let mut grid = $grid;
+                    grid.configure_cells();
+                    $on(&mut grid);
+                    assert_spanning_tree(&grid, $name);
+                }};
+            }
+
+            check!("grid", crate::grid::Grid::new(7, 9));
+            check!("cylinder", crate::grid::Grid::cylinder(5, 6));
+            check!("torus", crate::grid::Grid::torus(5, 6));
+            check!("moebius", crate::grid::Grid::moebius(5, 6));
+            check!("polar", crate::polar_grid::PolarGrid::new(6));
+            check!("hex", crate::hex_grid::HexGrid::new(6, 7));
+            check!("triangle", crate::triangle_grid::TriangleGrid::new(5, 9));
+            check!("upsilon", crate::upsilon_grid::UpsilonGrid::new(5, 5));
+            check!("cube", crate::cube_grid::CubeGrid::new(3));
+            check!("sphere", crate::sphere_grid::SphereGrid::new(8));
+            check!("3d", crate::grid3d::Grid3D::new(3, 4, 5));
+            check!("weave", crate::weave_grid::WeaveGrid::new(6, 6));
+            check!("voronoi", crate::voronoi_grid::VoronoiGrid::random(40, 10.0, 10.0));
+            check!(
+                "graph",
+                crate::graph_grid::GraphGrid::new(
+                    vec!["a", "b", "c", "d", "e", "f"],
+                    &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (1, 4)],
+                )
+                .unwrap()
+            );
+        }};
+    }
+    pub(crate) use assert_spans_every_grid;
+
+    /// A square grid with a hole punched through the middle.
+    pub fn holed_grid() -> Grid {
+        let mut mask = Mask::new(6, 6);
+        for (row, column) in [(2, 2), (2, 3), (3, 2), (3, 3), (0, 5)] {
+            mask.set(row, column, false);
+        }
+        let mut grid = Grid::masked(mask);
+        grid.configure_cells();
+        grid
+    }
+
+    #[test]
+    fn runs_close_north_and_carry_on_east() {
+        let mut grid = Grid::cylinder(3, 4);
+        grid.configure_cells();
+        let cell = grid.grid[1][3].clone();
+        let exit = grid.run_exit(&cell).unwrap();
+        assert_eq!((exit.borrow().row, exit.borrow().column), (0, 3));
+        // The seam is a neighbor, but no run carries on across it.
+        assert!(grid.run_next(&cell).is_none());
+        assert!(grid.run_exit(&grid.grid[0][1]).is_none());
+    }
+
+    #[test]
+    fn join_regions_links_stranded_cells() {
+        let mut grid = holed_grid();
+        join_regions(&mut grid);
+        assert_spanning_tree(&grid, "joined");
+    }
+
+    #[test]
+    fn wrapped_neighbors_are_symmetric() {
+        for wrap in [Wrap::Flat, Wrap::Cylinder, Wrap::Torus, Wrap::Moebius] {
+            for (rows, columns) in [(1, 1), (1, 4), (4, 1), (4, 5), (5, 4)] {
+                let mut grid = Grid::wrapped(rows, columns, wrap);
+                grid.configure_cells();
+                assert_symmetric(&grid);
+                for cell in grid.each_cell() {
+                    let neighbors = cell.borrow().neighbors();
+                    assert!(!neighbors.iter().any(|v| Rc::ptr_eq(v, &cell)));
+                    for (index, neighbor) in neighbors.iter().enumerate() {
+                        assert!(!neighbors[..index].iter().any(|v| Rc::ptr_eq(v, neighbor)));
                     }
-                    None => bottom_str.push_str("---"),
                 }
-
-                bottom_str.push('+');
             }
-            writeln!(f, "{}", top_str)?;
-            writeln!(f, "{}", bottom_str)?;
         }
+    }
+
+    #[test]
+    fn moebius_seam_turns_rows_over() {
+        let mut grid = Grid::moebius(4, 5);
+        grid.configure_cells();
+        let east = grid.east(&grid.grid[0][4].borrow()).unwrap();
+        assert_eq!((east.borrow().row, east.borrow().column), (3, 0));
+    }
 
-        Ok(())
+    #[test]
+    fn empty_grid_has_no_random_cell() {
+        let grid = Grid::new(0, 0);
+        assert!(grid.random_cell().is_none());
     }
 }
@@ -0,0 +1,160 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::random;
+use super::topology::*;
+
+/// A stack of `levels` square grids where every cell also borders the
+/// cells directly above and below it. Levels are stacked in `grid`, so a
+/// cell's `row` is `level * rows + row on the level`.
+pub struct Grid3D {
+    pub levels: usize,
+    pub rows: usize,
+    pub columns: usize,
+    pub grid: Vec<Vec<CellLink>>,
+    pub distance: Option<Distance>,
+}
+
+impl Grid3D {
+    pub fn new(levels: usize, rows: usize, columns: usize) -> Self {
+        let grid = (0..levels * rows)
+            .map(|row| {
+                (0..columns)
+                    .map(|column| Rc::new(RefCell::new(Cell::new(row, column))))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        Grid3D {
+            levels,
+            rows,
+            columns,
+            grid,
+            distance: None,
+        }
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.distance = Some(distance);
+    }
+
+    pub fn get_cell(&self, level: usize, row: usize, column: usize) -> Option<CellLink> {
+        if level >= self.levels || row >= self.rows || column >= self.columns {
+            None
+        } else {
+            Some(self.grid[level * self.rows + row][column].clone())
+        }
+    }
+
+    /// The level of `cell` and its row on that level.
+    pub fn level_of(&self, cell: &Cell) -> (usize, usize) {
+        (cell.row / self.rows, cell.row % self.rows)
+    }
+
+    pub fn north(&self, cell: &Cell) -> Option<CellLink> {
+        let (level, row) = self.level_of(cell);
+        row.checked_sub(1)
+            .and_then(|row| self.get_cell(level, row, cell.column))
+    }
+
+    pub fn south(&self, cell: &Cell) -> Option<CellLink> {
+        let (level, row) = self.level_of(cell);
+        self.get_cell(level, row + 1, cell.column)
+    }
+
+    pub fn east(&self, cell: &Cell) -> Option<CellLink> {
+        let (level, row) = self.level_of(cell);
+        self.get_cell(level, row, cell.column + 1)
+    }
+
+    pub fn west(&self, cell: &Cell) -> Option<CellLink> {
+        let (level, row) = self.level_of(cell);
+        cell.column
+            .checked_sub(1)
+            .and_then(|column| self.get_cell(level, row, column))
+    }
+
+    pub fn up(&self, cell: &Cell) -> Option<CellLink> {
+        let (level, row) = self.level_of(cell);
+        self.get_cell(level + 1, row, cell.column)
+    }
+
+    pub fn down(&self, cell: &Cell) -> Option<CellLink> {
+        let (level, row) = self.level_of(cell);
+        level
+            .checked_sub(1)
+            .and_then(|level| self.get_cell(level, row, cell.column))
+    }
+}
+
+impl MazeGrid for Grid3D {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.clone()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        if self.size() == 0 {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        let level: usize = rng.gen_range(0..self.levels);
+        let row: usize = rng.gen_range(0..self.rows);
+        let column: usize = rng.gen_range(0..self.columns);
+        self.get_cell(level, row, column)
+    }
+
+    fn size(&self) -> usize {
+        self.levels * self.rows * self.columns
+    }
+}
+
+impl Topology for Grid3D {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        [
+            self.north(cell),
+            self.south(cell),
+            self.east(cell),
+            self.west(cell),
+            self.up(cell),
+            self.down(cell),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
+
+impl Debug for Grid3D {
+    /// The grid's shape; the maze itself is drawn by a `Renderer`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Grid3D")
+            .field("levels", &self.levels)
+            .field("rows", &self.rows)
+            .field("columns", &self.columns)
+            .field("has_distance", &self.distance.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::tests::assert_symmetric;
+
+    #[test]
+    fn levels_join_up_and_down() {
+        let mut grid = Grid3D::new(3, 2, 2);
+        grid.configure_cells();
+        assert_symmetric(&grid);
+        // Two on its own level, one above and one below.
+        let middle = grid.each_cell()[4].clone();
+        assert_eq!(middle.borrow().neighbors().len(), 4);
+    }
+}
@@ -0,0 +1,128 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::random;
+use super::topology::*;
+
+/// A grid of flat-topped hexagons in offset coordinates: odd columns sit
+/// half a cell lower than even ones.
+pub struct HexGrid {
+    pub rows: usize,
+    pub columns: usize,
+    pub grid: Vec<Vec<CellLink>>,
+    pub distance: Option<Distance>,
+}
+
+impl HexGrid {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        let grid = (0..rows)
+            .map(|row| {
+                (0..columns)
+                    .map(|column| Rc::new(RefCell::new(Cell::new(row, column))))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        HexGrid {
+            rows,
+            columns,
+            grid,
+            distance: None,
+        }
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.distance = Some(distance);
+    }
+
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<CellLink> {
+        if row >= self.rows || column >= self.columns {
+            None
+        } else {
+            Some(self.grid[row][column].clone())
+        }
+    }
+
+    /// Rows holding the diagonal neighbors of `cell`, north then south;
+    /// `None` when that would be above the first row.
+    fn diagonal_rows(cell: &Cell) -> (Option<usize>, Option<usize>) {
+        if cell.column.is_multiple_of(2) {
+            (cell.row.checked_sub(1), Some(cell.row))
+        } else {
+            (Some(cell.row), Some(cell.row + 1))
+        }
+    }
+
+    pub fn north(&self, cell: &Cell) -> Option<CellLink> {
+        cell.row
+            .checked_sub(1)
+            .and_then(|row| self.get_cell(row, cell.column))
+    }
+
+    pub fn south(&self, cell: &Cell) -> Option<CellLink> {
+        self.get_cell(cell.row + 1, cell.column)
+    }
+
+    pub fn northeast(&self, cell: &Cell) -> Option<CellLink> {
+        let (north, _) = HexGrid::diagonal_rows(cell);
+        north.and_then(|row| self.get_cell(row, cell.column + 1))
+    }
+
+    pub fn northwest(&self, cell: &Cell) -> Option<CellLink> {
+        let (north, _) = HexGrid::diagonal_rows(cell);
+        let column = cell.column.checked_sub(1)?;
+        north.and_then(|row| self.get_cell(row, column))
+    }
+
+    pub fn southeast(&self, cell: &Cell) -> Option<CellLink> {
+        let (_, south) = HexGrid::diagonal_rows(cell);
+        south.and_then(|row| self.get_cell(row, cell.column + 1))
+    }
+
+    pub fn southwest(&self, cell: &Cell) -> Option<CellLink> {
+        let (_, south) = HexGrid::diagonal_rows(cell);
+        let column = cell.column.checked_sub(1)?;
+        south.and_then(|row| self.get_cell(row, column))
+    }
+}
+
+impl MazeGrid for HexGrid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.clone()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        if self.size() == 0 {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        let row: usize = rng.gen_range(0..self.rows);
+        let column: usize = rng.gen_range(0..self.columns);
+        self.get_cell(row, column)
+    }
+
+    fn size(&self) -> usize {
+        self.rows * self.columns
+    }
+}
+
+impl Topology for HexGrid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        [
+            self.north(cell),
+            self.south(cell),
+            self.northeast(cell),
+            self.northwest(cell),
+            self.southeast(cell),
+            self.southwest(cell),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
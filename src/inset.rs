@@ -0,0 +1,182 @@
+#![allow(dead_code)]
+use super::cell::*;
+use super::grid::*;
+
+/// The shape of the bends and dead ends of an inset corridor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Corners {
+    Square,
+    Rounded,
+}
+
+/// Draws corridors as bands inside the cells rather than walls as lines,
+/// for tile maps and laser cutting.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Inset {
+    /// How much of a cell is wall on each side, as a fraction of the cell
+    /// size between `0.0` and `0.5`.
+    pub amount: f64,
+    pub corners: Corners,
+}
+
+impl Default for Inset {
+    fn default() -> Self {
+        Inset {
+            amount: 0.15,
+            corners: Corners::Square,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Shape {
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    Circle {
+        x: f64,
+        y: f64,
+        radius: f64,
+    },
+}
+
+/// The floor of every corridor of `grid` with cells `size` across, each
+/// shape tagged with the `(row, column)` of the cell it belongs to.
+/// Passages through a wrapping seam run out to the edge of the grid.
+pub fn corridors(grid: &Grid, size: f64, inset: &Inset) -> Vec<((usize, usize), Shape)> {
+    let half = size * (0.5 - inset.amount.clamp(0.0, 0.5));
+    let linked = |cell: &Cell, neighbor: Option<CellLink>| -> Option<(usize, usize)> {
+        let neighbor = cell.linked(neighbor?)?;
+        let neighbor = neighbor.borrow();
+        Some((neighbor.row, neighbor.column))
+    };
+
+    let mut shapes = Vec::new();
+    for cell in grid.each_cell() {
+        let cell = cell.borrow();
+        let (row, column) = (cell.row, cell.column);
+        let (x, y) = ((column as f64 + 0.5) * size, (row as f64 + 0.5) * size);
+        let mut push = |shape: Shape| shapes.push(((row, column), shape));
+
+        push(match inset.corners {
+            Corners::Square => Shape::Rect {
+                x: x - half,
+                y: y - half,
+                width: 2.0 * half,
+                height: 2.0 * half,
+            },
+            Corners::Rounded => Shape::Circle { x, y, radius: half },
+        });
+
+        // East and south reach the next cell's center; the other two only
+        // matter across a seam, where every direction stops at the edge.
+        if let Some(east) = linked(&cell, grid.east(&cell)) {
+            let length = if east == (row, column + 1) { size } else { size / 2.0 };
+            push(Shape::Rect {
+                x,
+                y: y - half,
+                width: length,
+                height: 2.0 * half,
+            });
+        }
+        if let Some(south) = linked(&cell, grid.south(&cell)) {
+            let length = if south == (row + 1, column) { size } else { size / 2.0 };
+            push(Shape::Rect {
+                x: x - half,
+                y,
+                width: 2.0 * half,
+                height: length,
+            });
+        }
+        if let Some(west) = linked(&cell, grid.west(&cell)) {
+            if column == 0 || west != (row, column - 1) {
+                push(Shape::Rect {
+                    x: x - size / 2.0,
+                    y: y - half,
+                    width: size / 2.0,
+                    height: 2.0 * half,
+                });
+            }
+        }
+        if let Some(north) = linked(&cell, grid.north(&cell)) {
+            if row == 0 || north != (row - 1, column) {
+                push(Shape::Rect {
+                    x: x - half,
+                    y: y - size / 2.0,
+                    width: 2.0 * half,
+                    height: size / 2.0,
+                });
+            }
+        }
+    }
+
+    shapes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::Topology;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Shape {
+        Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn corridors_reach_the_next_center() {
+        let mut grid = Grid::new(2, 2);
+        grid.configure_cells();
+        let cell = |row, column| grid.get_cell(row, column).unwrap();
+        let (a, b, c) = (cell(0, 0), cell(0, 1), cell(1, 1));
+        grid.link(a, b.clone());
+        grid.link(b, c);
+
+        let inset = Inset {
+            amount: 0.25,
+            corners: Corners::Square,
+        };
+        let shapes = corridors(&grid, 10.0, &inset);
+        assert_eq!(
+            shapes,
+            vec![
+                ((0, 0), rect(2.5, 2.5, 5.0, 5.0)),
+                ((0, 0), rect(5.0, 2.5, 10.0, 5.0)),
+                ((0, 1), rect(12.5, 2.5, 5.0, 5.0)),
+                ((0, 1), rect(12.5, 5.0, 5.0, 10.0)),
+                ((1, 0), rect(2.5, 12.5, 5.0, 5.0)),
+                ((1, 1), rect(12.5, 12.5, 5.0, 5.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn seams_run_out_to_the_edge() {
+        let mut grid = Grid::cylinder(1, 3);
+        grid.configure_cells();
+        grid.link(grid.get_cell(0, 2).unwrap(), grid.get_cell(0, 0).unwrap());
+
+        let inset = Inset {
+            amount: 0.25,
+            corners: Corners::Rounded,
+        };
+        let shapes = corridors(&grid, 10.0, &inset);
+        assert_eq!(
+            shapes,
+            vec![
+                ((0, 0), Shape::Circle { x: 5.0, y: 5.0, radius: 2.5 }),
+                ((0, 0), rect(0.0, 2.5, 5.0, 5.0)),
+                ((0, 1), Shape::Circle { x: 15.0, y: 5.0, radius: 2.5 }),
+                ((0, 2), Shape::Circle { x: 25.0, y: 5.0, radius: 2.5 }),
+                ((0, 2), rect(25.0, 2.5, 5.0, 5.0)),
+            ]
+        );
+    }
+}
@@ -1,12 +1,14 @@
 #![allow(dead_code)]
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::Rng;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::rc::Weak;
 
 use super::cell::*;
 use super::grid::*;
+use super::random;
+use super::weave_grid::*;
 
 pub type KruskalLink = Rc<RefCell<KruskalNode>>;
 pub type WeakKruskalLink = Weak<RefCell<KruskalNode>>;
@@ -25,10 +27,7 @@ impl Downgradable<Option<WeakKruskalLink>> for Option<KruskalLink> {
 
 impl Upgradable<Option<KruskalLink>> for Option<WeakKruskalLink> {
     fn upgrade(self) -> Option<KruskalLink> {
-        match self.map(|v| v.upgrade()) {
-            None => None,
-            Some(v) => v,
-        }
+        self.map(|v| v.upgrade()).unwrap_or_default()
     }
 }
 
@@ -36,6 +35,7 @@ impl Upgradable<Option<KruskalLink>> for Option<WeakKruskalLink> {
 pub struct KruskalNode {
     pub row: usize,
     pub column: usize,
+    pub cell: CellLink,
     pub parent: Option<WeakKruskalLink>,
 }
 
@@ -66,7 +66,8 @@ impl PartialEq for KruskalNode {
 pub struct KruskalEdge {
     pub row: usize,
     pub column: usize,
-    pub horizontal: bool,
+    pub neighbor_row: usize,
+    pub neighbor_column: usize,
 }
 
 impl KruskalEdge {
@@ -81,15 +82,9 @@ impl KruskalEdge {
             None => return None,
         };
 
-        let (row1, column1) = if self.horizontal {
-            (self.row + 1, self.column)
-        } else {
-            (self.row, self.column + 1)
-        };
-
         let node2 = links.iter().find(|v| {
-            let cell = v.borrow().clone();
-            cell.row == row1 && cell.column == column1
+            let cell = v.borrow();
+            cell.row == self.neighbor_row && cell.column == self.neighbor_column
         });
 
         let node2 = match node2 {
@@ -107,82 +102,193 @@ pub struct Kruskal {
 }
 
 impl Kruskal {
-    pub fn new(grid: &Grid) -> Self {
-        let mut nodes: Vec<KruskalLink> = Vec::with_capacity(grid.rows * grid.columns);
-        let mut edges: Vec<KruskalEdge> =
-            Vec::with_capacity((grid.rows - 1) * (grid.columns - 1) * 2);
-
-        for row in 0..grid.rows {
-            for column in 0..grid.columns {
-                if row != grid.rows - 1 {
-                    let edge_h = KruskalEdge {
-                        row,
-                        column,
-                        horizontal: true,
-                    };
-                    edges.push(edge_h);
-                }
-                if column != grid.columns - 1 {
-                    let edge_v = KruskalEdge {
+    pub fn new<G: MazeGrid>(grid: &G) -> Self {
+        let cells = grid.each_cell();
+        let mut nodes: Vec<KruskalLink> = Vec::with_capacity(cells.len());
+        let mut edges: Vec<KruskalEdge> = Vec::with_capacity(cells.len() * 2);
+
+        for cell in cells {
+            let (row, column) = {
+                let cell_borrowed = cell.borrow();
+                (cell_borrowed.row, cell_borrowed.column)
+            };
+            let neighbors = grid.neighbors(&cell);
+
+            // Every pair of neighbors shows up twice; keep the one seen from
+            // the cell that comes first.
+            for neighbor in neighbors {
+                let neighbor = neighbor.borrow();
+                if (row, column) < (neighbor.row, neighbor.column) {
+                    edges.push(KruskalEdge {
                         row,
                         column,
-                        horizontal: false,
-                    };
-                    edges.push(edge_v);
+                        neighbor_row: neighbor.row,
+                        neighbor_column: neighbor.column,
+                    });
                 }
-
-                let node = KruskalNode {
-                    row,
-                    column,
-                    parent: None,
-                };
-                let node = Rc::new(RefCell::new(node));
-                nodes.push(node);
             }
+
+            let node = KruskalNode {
+                row,
+                column,
+                cell,
+                parent: None,
+            };
+            let node = Rc::new(RefCell::new(node));
+            nodes.push(node);
         }
-        edges.shuffle(&mut thread_rng());
+        edges.shuffle(&mut random::rng());
         Kruskal { nodes, edges }
     }
 }
 
-pub fn on(grid: &mut Grid) {
-    // Create Kruskal Structure
-    let mut kruskal = Kruskal::new(grid);
+impl Kruskal {
+    fn node(&self, row: usize, column: usize) -> Option<KruskalLink> {
+        self.nodes
+            .iter()
+            .find(|v| v.borrow().row == row && v.borrow().column == column)
+            .cloned()
+    }
+
+    fn root_of(&self, cell: &CellLink) -> Option<(usize, usize)> {
+        let cell = cell.borrow();
+        Some(self.node(cell.row, cell.column)?.borrow().root())
+    }
+
+    /// Puts the set holding `node1` under the one holding `node2`.
+    fn merge(&self, node1: &KruskalLink, node2: &KruskalLink) {
+        let node1_root = { node1.borrow().root() };
+        if let Some(root) = self.node(node1_root.0, node1_root.1) {
+            root.borrow_mut().parent = Some(node2.clone().downgrade());
+        }
+    }
+
+    fn merge_cells(&self, cell1: &CellLink, cell2: &CellLink) {
+        let (cell1, cell2) = (cell1.borrow(), cell2.borrow());
+        if let (Some(node1), Some(node2)) = (
+            self.node(cell1.row, cell1.column),
+            self.node(cell2.row, cell2.column),
+        ) {
+            self.merge(&node1, &node2);
+        }
+    }
+
+    /// Lays a crossing on `cell`: a corridor across it one way and a
+    /// tunnel under it the other. Only a cell with no passages yet whose
+    /// four neighbors are all in different sets will do, so the maze stays
+    /// a tree.
+    fn add_crossing(&mut self, grid: &mut WeaveGrid, cell: &CellLink) -> bool {
+        let sides = {
+            let cell = cell.borrow();
+            if !cell.links.is_empty() {
+                return false;
+            }
+            let grid = &grid.grid;
+            match (
+                grid.north(&cell),
+                grid.south(&cell),
+                grid.east(&cell),
+                grid.west(&cell),
+            ) {
+                (Some(north), Some(south), Some(east), Some(west)) => [north, south, east, west],
+                _ => return false,
+            }
+        };
 
-    while let Some(current) = kruskal.edges.pop() {
+        let mut roots = [cell, &sides[0], &sides[1], &sides[2], &sides[3]]
+            .iter()
+            .filter_map(|v| self.root_of(v))
+            .collect::<Vec<_>>();
+        roots.sort();
+        roots.dedup();
+        if roots.len() != 5 {
+            return false;
+        }
 
-    // loop {
-    //     let current = match kruskal.edges.pop() {
-    //         Some(v) => v,
-    //         None => break,
-    //     };
+        let [north, south, east, west] = sides;
+        let (over, under) = if random::rng().gen_bool(0.5) {
+            ((west, east), (north, south))
+        } else {
+            ((north, south), (west, east))
+        };
 
-        let find_result = current.find_node(&kruskal.nodes);
-        match find_result {
-            Some((node1, node2)) => {
+        grid.link(over.0.clone(), cell.clone());
+        grid.link(cell.clone(), over.1.clone());
+        grid.link(under.0.clone(), under.1.clone());
+        self.merge_cells(&over.0, cell);
+        self.merge_cells(cell, &over.1);
+        self.merge_cells(&under.0, &under.1);
+
+        // The crossing is finished; no other passage may join it.
+        let (row, column) = {
+            let cell = cell.borrow();
+            (cell.row, cell.column)
+        };
+        self.edges.retain(|v| {
+            (v.row, v.column) != (row, column) && (v.neighbor_row, v.neighbor_column) != (row, column)
+        });
+        true
+    }
+
+    fn run<G: MazeGrid>(&mut self, grid: &mut G) {
+        while let Some(current) = self.edges.pop() {
+            if let Some((node1, node2)) = current.find_node(&self.nodes) {
                 {
                     let node1_borrowed = node1.borrow();
                     let node2_borrowed = node2.borrow();
                     if node1_borrowed.root() == node2_borrowed.root() {
                         continue;
                     }
-                    link(
-                        grid.get_cell(node1_borrowed.row, node1_borrowed.column)
-                            .unwrap(),
-                        grid.get_cell(node2_borrowed.row, node2_borrowed.column)
-                            .unwrap(),
-                    );
+                    grid.link(node1_borrowed.cell.clone(), node2_borrowed.cell.clone());
                 }
-
-                let node1_root = { node1.borrow().root() };
-                let node1_root_borrow = kruskal
-                    .nodes
-                    .iter()
-                    .find(|v| v.borrow().row == node1_root.0 && v.borrow().column == node1_root.1)
-                    .unwrap();
-                node1_root_borrow.borrow_mut().parent = Some(node2.downgrade());
+                self.merge(&node1, &node2);
             }
-            None => (),
         }
     }
 }
+
+pub fn on<G: MazeGrid>(grid: &mut G) {
+    let mut kruskal = Kruskal::new(grid);
+    kruskal.run(grid);
+}
+
+/// Kruskal's algorithm on a weave grid. Its edges are fixed before any
+/// passage exists, so it never finds a tunnel by itself; instead, up to
+/// `attempts` random cells are tried as crossings first.
+pub fn weave(grid: &mut WeaveGrid, attempts: usize) {
+    let mut kruskal = Kruskal::new(grid);
+    for _ in 0..attempts {
+        if let Some(cell) = grid.random_cell() {
+            kruskal.add_crossing(grid, &cell);
+        }
+    }
+    kruskal.run(grid);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::tests::{assert_spanning_tree, assert_spans_every_grid, holed_grid};
+    use crate::topology::Topology;
+
+    #[test]
+    fn carves_a_spanning_tree_on_every_grid() {
+        assert_spans_every_grid!(super::on);
+    }
+
+    #[test]
+    fn carves_around_masked_cells() {
+        let mut grid = holed_grid();
+        super::on(&mut grid);
+        assert_spanning_tree(&grid, "masked");
+    }
+
+    #[test]
+    fn weave_lays_crossings() {
+        let mut grid = WeaveGrid::new(10, 10);
+        grid.configure_cells();
+        weave(&mut grid, 100);
+        assert!(!grid.under_cells.is_empty());
+        assert_spanning_tree(&grid, "weave");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{grid::Grid, distance::Distance};
+use crate::{grid::*, distance::Distance, renderer::*, topology::Topology};
 
 mod binary_tree;
 mod cell;
@@ -7,16 +7,44 @@ mod sidewinder;
 mod distance;
 mod recursive_backtracker;
 mod kruskal;
+mod braid;
+mod cull;
+mod mask;
+mod polar_grid;
+mod hex_grid;
+mod triangle_grid;
+mod upsilon_grid;
+mod topology;
+mod cube_grid;
+mod sphere_grid;
+mod grid3d;
+mod weave_grid;
+mod voronoi_grid;
+mod graph_grid;
+mod canvas;
+mod raster;
+mod svg;
+mod palette;
+mod unicode;
+mod ansi;
+mod ascii;
+mod renderer;
+mod inset;
+mod animation;
+mod geometry;
+mod random;
+mod pdf;
 
 fn main() {
     println!("Hello, world!");
+    let options = RenderOptions::default();
     let mut grid = Grid::new(10, 10);
     grid.configure_cells();
 
-    println!("{:?}", grid);
+    print!("{}", AsciiRenderer.render_to_string(&grid, &options));
 
     // binary_tree::on(&mut grid);
-    // println!("{:?}", grid);
+    // print!("{}", AsciiRenderer.render_to_string(&grid, &options));
 
     // let mut grid = Grid::new(8, 8);
     // grid.configure_cells();
@@ -24,11 +52,135 @@ fn main() {
     // sidewinder::on(&mut grid);
     // recursive_backtracker::on(&mut grid);
     kruskal::on(&mut grid);
-    println!("{:?}", grid);
+    print!("{}", AsciiRenderer.render_to_string(&grid, &options));
+
+    // braid::braid(&mut grid, 0.5);
+    // print!("{}", AsciiRenderer.render_to_string(&grid, &options));
+
+    // cull::cull(&mut grid, 5);
+    // print!("{}", AsciiRenderer.render_to_string(&grid, &options));
+
+    // let mut torus = Grid::torus(8, 8);
+    // torus.configure_cells();
+    // kruskal::on(&mut torus);
+    // print!("{}", AsciiRenderer.render_to_string(&torus, &options));
+
+    // let mut grid3d = grid3d::Grid3D::new(3, 5, 5);
+    // grid3d.configure_cells();
+    // recursive_backtracker::on(&mut grid3d);
+    // print!("{}", AsciiRenderer.render_to_string(&grid3d, &options));
+
+    // let mut weave_grid = weave_grid::WeaveGrid::new(10, 10);
+    // weave_grid.configure_cells();
+    // recursive_backtracker::on(&mut weave_grid);
+    // // or: kruskal::weave(&mut weave_grid, 100);
+    // print!("{}", AsciiRenderer.render_to_string(&weave_grid, &options));
+
+    // let mut polar_grid = polar_grid::PolarGrid::new(8);
+    // polar_grid.configure_cells();
+    // recursive_backtracker::on(&mut polar_grid);
+    // raster::render_polar(&polar_grid, &Default::default()).save_png("polar.png").unwrap();
+
+    // let mut hex_grid = hex_grid::HexGrid::new(8, 8);
+    // hex_grid.configure_cells();
+    // kruskal::on(&mut hex_grid);
+    // std::fs::write("hex.svg", svg::render_hex(&hex_grid, &Default::default())).unwrap();
+
+    // let mut triangle_grid = triangle_grid::TriangleGrid::new(8, 15);
+    // triangle_grid.configure_cells();
+    // sidewinder::on(&mut triangle_grid);
+    // print!("{}", AsciiRenderer.render_to_string(&triangle_grid, &options));
+
+    // let mut upsilon_grid = upsilon_grid::UpsilonGrid::new(9, 9);
+    // upsilon_grid.configure_cells();
+    // recursive_backtracker::on(&mut upsilon_grid);
+
+    // let mut voronoi_grid = voronoi_grid::VoronoiGrid::random(100, 20.0, 20.0);
+    // voronoi_grid.configure_cells();
+    // kruskal::on(&mut voronoi_grid);
+    // println!("{} dead ends", voronoi_grid.dead_ends().len());
+
+    // let stations = vec!["Bank", "Monument", "Tower Hill", "Aldgate", "Liverpool Street"];
+    // let mut graph_grid =
+    //     graph_grid::GraphGrid::new(stations, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (0, 3)])
+    //         .unwrap();
+    // graph_grid.configure_cells();
+    // recursive_backtracker::on(&mut graph_grid);
 
     let distance = Distance::distances(grid.get_cell(0, 0).unwrap());
     grid.set_distance(distance);
     // let breadcomb = distance.path_to(grid.get_cell(grid.rows-1, grid.columns-1).unwrap());
     // grid.set_distance(breadcomb);
-    println!("{:?}", grid);
+    print!("{}", AsciiRenderer.render_to_string(&grid, &options));
+
+    // print!("{}", unicode::render(&grid));
+    // print!("{}", unicode::render_half_blocks(&grid));
+    // print!("{}", ansi::render(&grid, &ansi::AnsiOptions::default()));
+
+    // let options = raster::RasterOptions {
+    //     palette: Some(palette::Palette::Viridis),
+    //     ..Default::default()
+    // };
+    // raster::render(&grid, &options).save_png("maze.png").unwrap();
+
+    // let mut grid = Grid::new(12, 12);
+    // grid.configure_cells();
+    // let mut recorder = animation::Recorder::new(&mut grid);
+    // recursive_backtracker::on(&mut recorder);
+    // let steps = recorder.steps;
+    // let options = raster::RasterOptions {
+    //     palette: Some(palette::Palette::Viridis),
+    //     ..Default::default()
+    // };
+    // let mut movie = animation::Animation::new(4);
+    // movie.carving(&grid, &steps, 1, &options);
+    // movie.hold(100);
+    // let (start, goal) = (grid.get_cell(0, 0).unwrap(), grid.get_cell(11, 11).unwrap());
+    // movie.solving(&mut grid, start, goal, &options);
+    // movie.hold(300);
+    // movie.save_gif("maze.gif").unwrap();
+
+    // let options = raster::RasterOptions {
+    //     inset: Some(inset::Inset {
+    //         amount: 0.2,
+    //         corners: inset::Corners::Rounded,
+    //     }),
+    //     ..Default::default()
+    // };
+    // raster::render(&grid, &options).save_png("tiles.png").unwrap();
+
+    // let goal = grid.get_cell(grid.rows - 1, grid.columns - 1).unwrap();
+    // let path = Distance::distances(grid.get_cell(0, 0).unwrap()).path_to(goal);
+    // let options = RenderOptions {
+    //     path: path.ordered().iter().map(|v| (v.row, v.column)).collect(),
+    //     ..Default::default()
+    // };
+    // print!("{}", UnicodeRenderer::default().render_to_string(&grid, &options));
+    // PngRenderer::default().render_to_file(&grid, &options, "maze.png".as_ref()).unwrap();
+
+    // let goal = grid.get_cell(grid.rows - 1, grid.columns - 1).unwrap();
+    // let path = Distance::distances(grid.get_cell(0, 0).unwrap()).path_to(goal);
+    // let options = svg::SvgOptions {
+    //     start: Some((0, 0)),
+    //     goal: Some((grid.rows - 1, grid.columns - 1)),
+    //     solution: path.ordered().iter().map(|v| (v.row, v.column)).collect(),
+    //     ..Default::default()
+    // };
+    // std::fs::write("maze.svg", svg::render(&grid, &options)).unwrap();
+
+    // for (number, seed) in [2024, 2025, 2026].into_iter().enumerate() {
+    //     let puzzle = pdf::Puzzle::generate(seed, || {
+    //         let mut grid = Grid::new(30, 20);
+    //         grid.configure_cells();
+    //         recursive_backtracker::on(&mut grid);
+    //         grid
+    //     });
+    //     let options = pdf::SheetOptions {
+    //         title: format!("Maze {}", number + 1),
+    //         paper: pdf::Paper::LETTER,
+    //         solution_page: true,
+    //         ..Default::default()
+    //     };
+    //     pdf::save_pdf(&puzzle, &options, format!("maze-{}.pdf", number + 1)).unwrap();
+    // }
 }
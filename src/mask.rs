@@ -0,0 +1,169 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use super::random;
+
+#[derive(Debug, Clone)]
+pub struct Mask {
+    pub rows: usize,
+    pub columns: usize,
+    pub bits: Vec<Vec<bool>>,
+}
+
+impl Mask {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Mask {
+            rows,
+            columns,
+            bits: vec![vec![true; columns]; rows],
+        }
+    }
+
+    /// Whether the cell at `(row, column)` is part of the maze.
+    /// Anything outside the mask counts as disabled.
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        if row >= self.rows || column >= self.columns {
+            false
+        } else {
+            self.bits[row][column]
+        }
+    }
+
+    /// Reads a mask from text where `X` marks a disabled cell and any other
+    /// character (usually `.`, but spaces too) an enabled one. Short lines
+    /// are padded with disabled cells, so a blank line is a row of them;
+    /// only blank lines at the end of the file are dropped.
+    pub fn from_txt<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let mut lines = text.lines().collect::<Vec<_>>();
+        while lines.last().is_some_and(|v| v.is_empty()) {
+            lines.pop();
+        }
+
+        let rows = lines.len();
+        let columns = lines.iter().map(|v| v.chars().count()).max().unwrap_or(0);
+        let mut mask = Mask::new(rows, columns);
+
+        for (row, line) in lines.iter().enumerate() {
+            for column in 0..columns {
+                let enabled = match line.chars().nth(column) {
+                    Some(v) => v != 'X' && v != 'x',
+                    None => false,
+                };
+                mask.set(row, column, enabled);
+            }
+        }
+
+        Ok(mask)
+    }
+
+    /// Reads a mask from an image where black pixels are disabled cells and
+    /// every other pixel an enabled one, one pixel per cell.
+    pub fn from_png<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut decoder = png::Decoder::new(File::open(path)?);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer)?;
+
+        let samples = info.color_type.samples();
+        let rows = info.height as usize;
+        let columns = info.width as usize;
+        let mut mask = Mask::new(rows, columns);
+
+        for row in 0..rows {
+            let line = &buffer[row * info.line_size..(row + 1) * info.line_size];
+            for column in 0..columns {
+                let pixel = &line[column * samples..(column + 1) * samples];
+                // Grayscale images carry one color sample, RGB(A) three.
+                let colors = if samples < 3 { &pixel[..1] } else { &pixel[..3] };
+                let black = colors.iter().all(|v| *v < 128);
+                mask.set(row, column, !black);
+            }
+        }
+
+        Ok(mask)
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, enabled: bool) {
+        if row < self.rows && column < self.columns {
+            self.bits[row][column] = enabled;
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.bits.iter().flatten().filter(|v| **v).count()
+    }
+
+    pub fn random_location(&self) -> Option<(usize, usize)> {
+        if self.count() == 0 {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        loop {
+            let row: usize = rng.gen_range(0..self.rows);
+            let column: usize = rng.gen_range(0..self.columns);
+            if self.bits[row][column] {
+                return Some((row, column));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::io::BufWriter;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("maze-mask-{}-{}", std::process::id(), name))
+    }
+
+    fn bits(mask: &Mask) -> Vec<String> {
+        mask.bits
+            .iter()
+            .map(|row| row.iter().map(|v| if *v { '.' } else { 'X' }).collect())
+            .collect()
+    }
+
+    #[test]
+    fn text_keeps_blank_rows_and_spaces() {
+        let path = temp_path("mask.txt");
+        fs::write(&path, "X..\n\n. X \n.\n\n\n").unwrap();
+        let mask = Mask::from_txt(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!((mask.rows, mask.columns), (4, 4));
+        assert_eq!(bits(&mask), vec!["X..X", "XXXX", "..X.", ".XXX"]);
+        assert_eq!(mask.count(), 6);
+    }
+
+    #[test]
+    fn png_disables_dark_pixels() {
+        let path = temp_path("mask.png");
+        {
+            let file = File::create(&path).unwrap();
+            let mut encoder = png::Encoder::new(BufWriter::new(file), 3, 2);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            #[rustfmt::skip]
+            let pixels = [
+                0, 0, 0,  255, 255, 255,  200, 30, 30,
+                255, 255, 255,  20, 20, 20,  127, 127, 127,
+            ];
+            writer.write_image_data(&pixels).unwrap();
+        }
+        let mask = Mask::from_png(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!((mask.rows, mask.columns), (2, 3));
+        assert_eq!(bits(&mask), vec!["X..", ".XX"]);
+    }
+}
@@ -0,0 +1,98 @@
+#![allow(dead_code)]
+use super::canvas::Color;
+
+/// Colors for distances, from the root (`0.0`) to the farthest cell (`1.0`).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Palette {
+    Viridis,
+    Magma,
+    Grayscale,
+    /// A straight blend between two colors.
+    Gradient(Color, Color),
+}
+
+/// Start and goal markers and the solution path, in every image format.
+pub const START: Color = Color::rgb(42, 157, 58);
+pub const GOAL: Color = Color::rgb(220, 40, 40);
+pub const SOLUTION: Color = Color::rgb(70, 110, 230);
+
+// Evenly spaced samples of matplotlib's colormaps, blended in between.
+const VIRIDIS: [Color; 9] = [
+    Color::rgb(68, 1, 84),
+    Color::rgb(71, 44, 122),
+    Color::rgb(59, 81, 139),
+    Color::rgb(44, 113, 142),
+    Color::rgb(33, 144, 141),
+    Color::rgb(39, 173, 129),
+    Color::rgb(92, 200, 99),
+    Color::rgb(170, 220, 50),
+    Color::rgb(253, 231, 37),
+];
+
+const MAGMA: [Color; 9] = [
+    Color::rgb(0, 0, 4),
+    Color::rgb(28, 16, 68),
+    Color::rgb(79, 18, 123),
+    Color::rgb(129, 37, 129),
+    Color::rgb(181, 54, 122),
+    Color::rgb(229, 80, 100),
+    Color::rgb(251, 135, 97),
+    Color::rgb(254, 194, 135),
+    Color::rgb(252, 253, 191),
+];
+
+fn blend(from: Color, to: Color, t: f64) -> Color {
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    Color::rgb(mix(from.r, to.r), mix(from.g, to.g), mix(from.b, to.b))
+}
+
+fn sample(stops: &[Color], t: f64) -> Color {
+    let position = t * (stops.len() - 1) as f64;
+    let index = (position.floor() as usize).min(stops.len() - 2);
+    blend(stops[index], stops[index + 1], position - index as f64)
+}
+
+impl Palette {
+    pub fn color(&self, t: f64) -> Color {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        match self {
+            Palette::Viridis => sample(&VIRIDIS, t),
+            Palette::Magma => sample(&MAGMA, t),
+            Palette::Grayscale => blend(Color::WHITE, Color::BLACK, t),
+            Palette::Gradient(from, to) => blend(*from, *to, t),
+        }
+    }
+
+    /// The color for `distance` on a scale that ends at `max`.
+    pub fn distance_color(&self, distance: usize, max: usize) -> Color {
+        if max == 0 {
+            self.color(0.0)
+        } else {
+            self.color(distance as f64 / max as f64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palettes_run_from_first_to_last_stop() {
+        assert_eq!(Palette::Viridis.color(0.0), VIRIDIS[0]);
+        assert_eq!(Palette::Viridis.color(1.0), VIRIDIS[8]);
+        assert_eq!(Palette::Magma.color(2.0), MAGMA[8]);
+        assert_eq!(Palette::Grayscale.color(f64::NAN), Color::WHITE);
+
+        let (from, to) = (Color::rgb(0, 100, 200), Color::rgb(200, 100, 0));
+        assert_eq!(Palette::Gradient(from, to).color(0.5), Color::rgb(100, 100, 100));
+    }
+
+    #[test]
+    fn distances_scale_to_the_farthest_cell() {
+        let palette = Palette::Gradient(Color::BLACK, Color::rgb(200, 200, 200));
+        assert_eq!(palette.distance_color(0, 0), Color::BLACK);
+        assert_eq!(palette.distance_color(1, 4), Color::rgb(50, 50, 50));
+        assert_eq!(palette.distance_color(4, 4), Color::rgb(200, 200, 200));
+    }
+}
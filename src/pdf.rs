@@ -0,0 +1,572 @@
+#![allow(dead_code)]
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::canvas::Color;
+use super::distance::*;
+use super::grid::*;
+use super::palette::{GOAL, SOLUTION, START};
+use super::random;
+use super::svg::merge_runs;
+
+/// A page size in points, 72 to the inch.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Paper {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Paper {
+    pub const A4: Paper = Paper::mm(210.0, 297.0);
+    pub const A5: Paper = Paper::mm(148.0, 210.0);
+    pub const LETTER: Paper = Paper {
+        width: 612.0,
+        height: 792.0,
+    };
+
+    pub const fn mm(width: f64, height: f64) -> Self {
+        Paper {
+            width: width * MM,
+            height: height * MM,
+        }
+    }
+
+    pub const fn landscape(self) -> Self {
+        Paper {
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+/// Points in a millimetre.
+pub const MM: f64 = 72.0 / 25.4;
+
+/// Blank space at each edge of the page, in points.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Margins {
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+impl Margins {
+    pub const fn uniform(points: f64) -> Self {
+        Margins {
+            top: points,
+            right: points,
+            bottom: points,
+            left: points,
+        }
+    }
+}
+
+/// A maze to print, with the seed it was carved from when there is one.
+pub struct Puzzle {
+    pub grid: Grid,
+    pub seed: Option<u64>,
+}
+
+impl Puzzle {
+    /// Restarts the shared random stream at `seed` before `make` builds and
+    /// carves the grid, so the seed printed on the sheet makes the same
+    /// maze again.
+    pub fn generate<F: FnOnce() -> Grid>(seed: u64, make: F) -> Self {
+        random::seed(seed);
+        Puzzle {
+            grid: make(),
+            seed: Some(seed),
+        }
+    }
+}
+
+impl From<Grid> for Puzzle {
+    /// A maze of unknown origin; its sheet has no seed on it.
+    fn from(grid: Grid) -> Self {
+        Puzzle { grid, seed: None }
+    }
+}
+
+pub struct SheetOptions {
+    pub title: String,
+    pub paper: Paper,
+    pub margins: Margins,
+    pub wall_width: f64,
+    /// Entrance and exit as `(row, column)`; when either is missing, the
+    /// ends of the longest path are used.
+    pub start: Option<(usize, usize)>,
+    pub goal: Option<(usize, usize)>,
+    /// Adds a second page with the solution drawn in.
+    pub solution_page: bool,
+}
+
+impl Default for SheetOptions {
+    fn default() -> Self {
+        SheetOptions {
+            title: String::from("Maze"),
+            paper: Paper::A4,
+            margins: Margins::uniform(15.0 * MM),
+            wall_width: 1.5,
+            start: None,
+            goal: None,
+            solution_page: false,
+        }
+    }
+}
+
+/// Points taken by the title and stats above the maze.
+const HEADER: f64 = 64.0;
+
+/// How hard a maze is to solve between two cells.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Difficulty {
+    pub cells: usize,
+    /// Cells on the solution, both ends included.
+    pub solution: usize,
+    pub dead_ends: usize,
+    /// Cells on the solution where there is more than one way on.
+    pub decisions: usize,
+}
+
+impl Difficulty {
+    pub fn of(grid: &Grid, solution: &[(usize, usize)]) -> Self {
+        let decisions = solution
+            .iter()
+            .enumerate()
+            .take(solution.len().saturating_sub(1))
+            .filter(|(index, (row, column))| {
+                let links = grid
+                    .get_cell(*row, *column)
+                    .map_or(0, |v| v.borrow().links.len());
+                // Everywhere but the start, one passage is the way in.
+                let ways_on = if *index == 0 { links } else { links.saturating_sub(1) };
+                ways_on > 1
+            })
+            .count();
+
+        Difficulty {
+            cells: grid.size(),
+            solution: solution.len(),
+            dead_ends: grid.dead_ends().len(),
+            decisions,
+        }
+    }
+}
+
+/// The ends of the longest path through the grid.
+pub fn longest_path(grid: &Grid) -> Option<((usize, usize), (usize, usize))> {
+    let farthest = |distance: &Distance| {
+        distance
+            .ordered()
+            .last()
+            .map(|v| (v.row, v.column))
+    };
+
+    let first = grid.each_cell().into_iter().next()?;
+    let start = farthest(&Distance::distances(first))?;
+    let goal = farthest(&Distance::distances(grid.get_cell(start.0, start.1)?))?;
+    Some((start, goal))
+}
+
+/// Escapes text for a PDF string in WinAnsi encoding; characters it has no
+/// code for come out as `?`.
+fn pdf_string(text: &str) -> String {
+    let mut escaped = String::new();
+    for v in text.chars() {
+        match v {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(v);
+            }
+            ' '..='~' => escaped.push(v),
+            '\u{a0}'..='\u{ff}' => escaped.push_str(&format!("\\{:03o}", v as u32)),
+            '\u{2018}' => escaped.push_str("\\221"),
+            '\u{2019}' => escaped.push_str("\\222"),
+            '\u{201c}' => escaped.push_str("\\223"),
+            '\u{201d}' => escaped.push_str("\\224"),
+            '\u{2022}' => escaped.push_str("\\225"),
+            '\u{2013}' => escaped.push_str("\\226"),
+            '\u{2014}' => escaped.push_str("\\227"),
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+fn rgb(color: Color) -> String {
+    format!(
+        "{:.3} {:.3} {:.3}",
+        color.r as f64 / 255.0,
+        color.g as f64 / 255.0,
+        color.b as f64 / 255.0
+    )
+}
+
+/// A filled circle, as four Bézier quarters.
+fn circle((x, y): (f64, f64), radius: f64, color: Color) -> String {
+    let k = radius * 0.5523;
+    format!(
+        "{} rg\n{:.2} {:.2} m\n\
+         {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c\n\
+         {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c\n\
+         {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c\n\
+         {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c\nf\n",
+        rgb(color),
+        x + radius,
+        y,
+        x + radius,
+        y + k,
+        x + k,
+        y + radius,
+        x,
+        y + radius,
+        x - k,
+        y + radius,
+        x - radius,
+        y + k,
+        x - radius,
+        y,
+        x - radius,
+        y - k,
+        x - k,
+        y - radius,
+        x,
+        y - radius,
+        x + k,
+        y - radius,
+        x + radius,
+        y - k,
+        x + radius,
+        y
+    )
+}
+
+fn text(x: f64, y: f64, font: &str, size: f64, line: &str) -> String {
+    format!(
+        "BT /{} {} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+        font,
+        size,
+        x,
+        y,
+        pdf_string(line)
+    )
+}
+
+/// The drawing commands for one page; `solution` is drawn in when given.
+fn page(
+    grid: &Grid,
+    options: &SheetOptions,
+    title: &str,
+    stats: &[String],
+    ends: Option<((usize, usize), (usize, usize))>,
+    solution: Option<&[(usize, usize)]>,
+) -> String {
+    let (paper, margins) = (options.paper, options.margins);
+    let mut content = String::new();
+
+    let top = paper.height - margins.top;
+    content.push_str("0 g\n");
+    content.push_str(&text(margins.left, top - 20.0, "F2", 20.0, title));
+    for (index, line) in stats.iter().enumerate() {
+        content.push_str(&text(
+            margins.left,
+            top - 38.0 - 13.0 * index as f64,
+            "F1",
+            10.0,
+            line,
+        ));
+    }
+
+    // The maze fills what is left, centered across and hung from the top.
+    let width = paper.width - margins.left - margins.right;
+    let height = paper.height - margins.top - margins.bottom - HEADER;
+    let size = (width / grid.columns.max(1) as f64).min(height / grid.rows.max(1) as f64);
+    let left = margins.left + (width - size * grid.columns as f64) / 2.0;
+    let top = top - HEADER;
+    let center = |(row, column): (usize, usize)| {
+        (
+            left + (column as f64 + 0.5) * size,
+            top - (row as f64 + 0.5) * size,
+        )
+    };
+
+    if let Some(solution) = solution {
+        content.push_str(&format!(
+            "{} RG\n{:.2} w 1 J 1 j\n",
+            rgb(SOLUTION),
+            size / 4.0
+        ));
+        for run in split_at_seams(solution) {
+            for (index, cell) in run.iter().enumerate() {
+                let (x, y) = center(*cell);
+                content.push_str(&format!(
+                    "{:.2} {:.2} {}\n",
+                    x,
+                    y,
+                    if index == 0 { "m" } else { "l" }
+                ));
+            }
+        }
+        content.push_str("S\n");
+    }
+
+    let (horizontal, vertical) = grid.walls();
+    content.push_str(&format!("0 G\n{:.2} w 2 J 0 j\n", options.wall_width));
+    content.push_str(&merge_runs(&horizontal, |row, from, to| {
+        let y = top - row as f64 * size;
+        format!(
+            "{:.2} {:.2} m {:.2} {:.2} l\n",
+            left + from as f64 * size,
+            y,
+            left + to as f64 * size,
+            y
+        )
+    }));
+    content.push_str(&merge_runs(&vertical, |column, from, to| {
+        let x = left + column as f64 * size;
+        format!(
+            "{:.2} {:.2} m {:.2} {:.2} l\n",
+            x,
+            top - from as f64 * size,
+            x,
+            top - to as f64 * size
+        )
+    }));
+    content.push_str("S\n");
+
+    if let Some((start, goal)) = ends {
+        content.push_str(&circle(center(start), size / 4.0, START));
+        content.push_str(&circle(center(goal), size / 4.0, GOAL));
+    }
+    content
+}
+
+/// The solution from `start` to `goal`; an error if either is not a cell
+/// of the grid or there is no way between them.
+fn solve(grid: &Grid, start: (usize, usize), goal: (usize, usize)) -> io::Result<Vec<(usize, usize)>> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidInput, message);
+    let start = grid
+        .get_cell(start.0, start.1)
+        .ok_or_else(|| invalid("start is not a cell of the grid"))?;
+    let goal = grid
+        .get_cell(goal.0, goal.1)
+        .ok_or_else(|| invalid("goal is not a cell of the grid"))?;
+
+    let distances = Distance::distances(start);
+    if distances.get(goal.clone()).is_none() {
+        return Err(invalid("goal cannot be reached from start"));
+    }
+    Ok(distances
+        .path_to(goal)
+        .ordered()
+        .iter()
+        .map(|v| (v.row, v.column))
+        .collect::<Vec<_>>())
+}
+
+/// Lays the maze out as a PDF puzzle sheet: title, seed and difficulty
+/// above the maze, and optionally the solution on a second page. A maze
+/// with no way from start to goal is an error rather than a sheet.
+pub fn render(puzzle: &Puzzle, options: &SheetOptions) -> io::Result<Vec<u8>> {
+    let grid = &puzzle.grid;
+    let ends = match (options.start, options.goal) {
+        (Some(start), Some(goal)) => Some((start, goal)),
+        _ => longest_path(grid),
+    };
+    let solution = match ends {
+        Some((start, goal)) => solve(grid, start, goal)?,
+        None => Vec::new(),
+    };
+
+    let difficulty = Difficulty::of(grid, &solution);
+    let mut first = format!("{} \u{d7} {} cells", grid.rows, grid.columns);
+    if let Some(seed) = puzzle.seed {
+        first = format!("Seed {}   \u{2022}   {}", seed, first);
+    }
+    let stats = [
+        first,
+        format!(
+            "Solution {} of {} cells ({}%)   \u{2022}   {} decisions   \u{2022}   {} dead ends",
+            difficulty.solution,
+            difficulty.cells,
+            (100 * difficulty.solution).checked_div(difficulty.cells).unwrap_or(0),
+            difficulty.decisions,
+            difficulty.dead_ends
+        ),
+    ];
+
+    let mut pages = vec![page(grid, options, &options.title, &stats, ends, None)];
+    if options.solution_page {
+        let title = format!("{} \u{2014} Solution", options.title);
+        pages.push(page(grid, options, &title, &stats, ends, Some(&solution)));
+    }
+
+    // Objects 1 to 4 are the catalog, page tree and fonts, 5 the document
+    // info, then a page and its contents for each page.
+    let kids = (0..pages.len())
+        .map(|v| format!("{} 0 R", 6 + 2 * v))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut objects = vec![
+        String::from("<< /Type /Catalog /Pages 2 0 R >>"),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} /MediaBox [0 0 {:.2} {:.2}] >>",
+            kids,
+            pages.len(),
+            options.paper.width,
+            options.paper.height
+        ),
+        String::from(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>",
+        ),
+        String::from(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>",
+        ),
+        format!("<< /Title ({}) >>", pdf_string(&options.title)),
+    ];
+    for (index, content) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            7 + 2 * index
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+    }
+
+    let xref = pdf.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        table.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    table.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    ));
+    pdf.extend_from_slice(table.as_bytes());
+    Ok(pdf)
+}
+
+pub fn write_pdf<W: Write>(puzzle: &Puzzle, options: &SheetOptions, mut writer: W) -> io::Result<()> {
+    writer.write_all(&render(puzzle, options)?)?;
+    writer.flush()
+}
+
+/// Writes nothing when the sheet cannot be made.
+pub fn save_pdf<P: AsRef<Path>>(puzzle: &Puzzle, options: &SheetOptions, path: P) -> io::Result<()> {
+    let pdf = render(puzzle, options)?;
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(&pdf)?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recursive_backtracker;
+    use crate::topology::Topology;
+
+    fn position(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|v| v == needle)
+    }
+
+    fn puzzle(seed: u64) -> Puzzle {
+        Puzzle::generate(seed, || {
+            let mut grid = Grid::new(8, 10);
+            grid.configure_cells();
+            recursive_backtracker::on(&mut grid);
+            grid
+        })
+    }
+
+    #[test]
+    fn xref_points_at_every_object() {
+        let options = SheetOptions {
+            solution_page: true,
+            ..Default::default()
+        };
+        let pdf = render(&puzzle(7), &options).unwrap();
+
+        let tail = String::from_utf8_lossy(&pdf[pdf.len() - 40..]).to_string();
+        let startxref = tail
+            .lines()
+            .skip_while(|v| *v != "startxref")
+            .nth(1)
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap();
+        assert!(pdf[startxref..].starts_with(b"xref\n"));
+
+        let table = String::from_utf8_lossy(&pdf[startxref..]).to_string();
+        let entries = table
+            .lines()
+            .skip(3)
+            .take_while(|v| v.ends_with(" n "))
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 9);
+        for (index, entry) in entries.iter().enumerate() {
+            assert_eq!(entry.len(), 19);
+            let offset = entry[..10].parse::<usize>().unwrap();
+            let header = format!("{} 0 obj\n", index + 1);
+            assert!(pdf[offset..].starts_with(header.as_bytes()), "object {}", index + 1);
+        }
+    }
+
+    #[test]
+    fn stream_lengths_match_contents() {
+        let pdf = render(&puzzle(11), &SheetOptions::default()).unwrap();
+        let mut rest = &pdf[..];
+        let mut streams = 0;
+        while let Some(at) = position(rest, b"<< /Length ") {
+            rest = &rest[at + 11..];
+            let digits = rest.iter().take_while(|v| v.is_ascii_digit()).count();
+            let length = std::str::from_utf8(&rest[..digits]).unwrap().parse::<usize>().unwrap();
+            let start = position(rest, b"stream\n").unwrap() + 7;
+            assert!(rest[start + length..].starts_with(b"endstream"));
+            streams += 1;
+        }
+        assert_eq!(streams, 1);
+    }
+
+    #[test]
+    fn same_seed_prints_the_same_sheet() {
+        let options = SheetOptions::default();
+        assert_eq!(render(&puzzle(3), &options).unwrap(), render(&puzzle(3), &options).unwrap());
+        assert!(position(&render(&puzzle(3), &options).unwrap(), b"(Seed 3 ").is_some());
+    }
+
+    #[test]
+    fn unreachable_goal_is_an_error() {
+        let mut grid = Grid::new(4, 4);
+        grid.configure_cells();
+        let options = SheetOptions {
+            start: Some((0, 0)),
+            goal: Some((3, 3)),
+            ..Default::default()
+        };
+        let error = render(&Puzzle::from(grid), &options).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn ends_outside_the_grid_are_an_error() {
+        let options = SheetOptions {
+            start: Some((0, 0)),
+            goal: Some((8, 0)),
+            ..Default::default()
+        };
+        assert!(render(&puzzle(5), &options).is_err());
+    }
+}
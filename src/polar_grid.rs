@@ -0,0 +1,129 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::f64::consts::PI;
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::random;
+use super::topology::*;
+
+/// A circular grid: row 0 is the single cell at the center and every
+/// further row is a ring, subdivided so its cells stay roughly square.
+pub struct PolarGrid {
+    pub rows: usize,
+    pub grid: Vec<Vec<CellLink>>,
+    pub distance: Option<Distance>,
+}
+
+impl PolarGrid {
+    pub fn new(rows: usize) -> Self {
+        let mut grid: Vec<Vec<CellLink>> = Vec::with_capacity(rows);
+        if rows > 0 {
+            grid.push(vec![Rc::new(RefCell::new(Cell::new(0, 0)))]);
+        }
+
+        let row_height = 1.0 / rows as f64;
+        for row in 1..rows {
+            let radius = row as f64 / rows as f64;
+            let circumference = 2.0 * PI * radius;
+
+            let previous_count = grid[row - 1].len();
+            let estimated_cell_width = circumference / previous_count as f64;
+            let ratio = (estimated_cell_width / row_height).round().max(1.0) as usize;
+
+            let row_grid = (0..previous_count * ratio)
+                .map(|column| Rc::new(RefCell::new(Cell::new(row, column))))
+                .collect::<Vec<_>>();
+            grid.push(row_grid);
+        }
+
+        PolarGrid {
+            rows,
+            grid,
+            distance: None,
+        }
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.distance = Some(distance);
+    }
+
+    /// Columns wrap around the ring.
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<CellLink> {
+        if row >= self.rows {
+            None
+        } else {
+            let count = self.grid[row].len();
+            Some(self.grid[row][column % count].clone())
+        }
+    }
+
+    pub fn cw(&self, cell: &Cell) -> Option<CellLink> {
+        if cell.row == 0 {
+            None
+        } else {
+            self.get_cell(cell.row, cell.column + 1)
+        }
+    }
+
+    pub fn ccw(&self, cell: &Cell) -> Option<CellLink> {
+        if cell.row == 0 {
+            None
+        } else {
+            let count = self.grid[cell.row].len();
+            self.get_cell(cell.row, cell.column + count - 1)
+        }
+    }
+
+    pub fn inward(&self, cell: &Cell) -> Option<CellLink> {
+        if cell.row == 0 {
+            None
+        } else {
+            let ratio = self.grid[cell.row].len() / self.grid[cell.row - 1].len();
+            self.get_cell(cell.row - 1, cell.column / ratio)
+        }
+    }
+
+    pub fn outward(&self, cell: &Cell) -> Vec<CellLink> {
+        if cell.row + 1 >= self.rows {
+            Vec::new()
+        } else {
+            let ratio = self.grid[cell.row + 1].len() / self.grid[cell.row].len();
+            self.grid[cell.row + 1][cell.column * ratio..(cell.column + 1) * ratio].to_vec()
+        }
+    }
+}
+
+impl MazeGrid for PolarGrid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.clone()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        if self.rows == 0 {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        let row: usize = rng.gen_range(0..self.rows);
+        let column: usize = rng.gen_range(0..self.grid[row].len());
+        self.get_cell(row, column)
+    }
+
+    fn size(&self) -> usize {
+        self.grid.iter().map(|v| v.len()).sum()
+    }
+}
+
+impl Topology for PolarGrid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        let mut result = [self.cw(cell), self.ccw(cell), self.inward(cell)]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        result.extend(self.outward(cell));
+        result
+    }
+}
@@ -0,0 +1,50 @@
+#![allow(dead_code)]
+use std::cell::{Cell, RefCell};
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+// Every generator draws from this one stream, so a maze can be made again
+// from its seed. It starts from a random seed that `current_seed` reports.
+thread_local! {
+    static SEED: Cell<u64> = Cell::new(rand::random());
+    static STATE: RefCell<StdRng> = RefCell::new(StdRng::seed_from_u64(SEED.with(Cell::get)));
+}
+
+/// Restarts the stream, so the mazes carved after it come out the same for
+/// the same seed.
+pub fn seed(value: u64) {
+    SEED.with(|v| v.set(value));
+    STATE.with(|v| *v.borrow_mut() = StdRng::seed_from_u64(value));
+}
+
+/// The seed the stream last started from.
+pub fn current_seed() -> u64 {
+    SEED.with(Cell::get)
+}
+
+/// A handle on the shared stream, used where `rand::thread_rng()` would be.
+pub fn rng() -> Random {
+    Random
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Random;
+
+impl RngCore for Random {
+    fn next_u32(&mut self) -> u32 {
+        STATE.with(|v| v.borrow_mut().next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        STATE.with(|v| v.borrow_mut().next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        STATE.with(|v| v.borrow_mut().fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        STATE.with(|v| v.borrow_mut().try_fill_bytes(dest))
+    }
+}
@@ -0,0 +1,327 @@
+#![allow(dead_code)]
+use super::canvas::*;
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::geometry::{self, Layout};
+use super::grid3d::*;
+use super::hex_grid::*;
+use super::inset::*;
+use super::polar_grid::*;
+use super::palette::*;
+
+pub struct RasterOptions {
+    /// Pixels from one wall to the next.
+    pub cell_size: usize,
+    pub wall_thickness: usize,
+    pub background: Color,
+    pub wall: Color,
+    /// Colors each cell by the grid's `distance`, when it has one.
+    pub palette: Option<Palette>,
+    /// Cells the solution passes through, in order.
+    pub solution: Vec<(usize, usize)>,
+    pub solution_color: Color,
+    /// Draws corridors as inset bands instead of walls as lines; the wall
+    /// thickness then comes from the inset.
+    pub inset: Option<Inset>,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        RasterOptions {
+            cell_size: 20,
+            wall_thickness: 2,
+            background: Color::WHITE,
+            wall: Color::BLACK,
+            palette: None,
+            solution: Vec::new(),
+            solution_color: SOLUTION,
+            inset: None,
+        }
+    }
+}
+
+/// Fills every cell `distance` reaches with its palette color, before any
+/// walls go on top. `origin` gives the top-left corner of a cell.
+fn draw_heatmap<F: Fn(&Cell) -> (usize, usize)>(
+    canvas: &mut Canvas,
+    cells: &[CellLink],
+    distance: Option<&Distance>,
+    origin: F,
+    options: &RasterOptions,
+) {
+    let (Some(palette), Some(distance)) = (options.palette, distance) else {
+        return;
+    };
+
+    let max = distance.max();
+    for cell in cells {
+        if let Some(value) = distance.get(cell.clone()) {
+            let (x, y) = origin(&cell.borrow());
+            let size = options.cell_size;
+            canvas.fill_rect(x, y, size, size, palette.distance_color(value, max));
+        }
+    }
+}
+
+/// Draws the walls of the square cell whose top-left corner is at `(x, y)`.
+/// `walls` is north, south, east, west.
+fn draw_walls(canvas: &mut Canvas, x: usize, y: usize, walls: [bool; 4], options: &RasterOptions) {
+    let (size, thickness) = (options.cell_size, options.wall_thickness);
+    let [north, south, east, west] = walls;
+
+    if north {
+        canvas.fill_rect(x, y, size + thickness, thickness, options.wall);
+    }
+    if south {
+        canvas.fill_rect(x, y + size, size + thickness, thickness, options.wall);
+    }
+    if east {
+        canvas.fill_rect(x + size, y, thickness, size + thickness, options.wall);
+    }
+    if west {
+        canvas.fill_rect(x, y, thickness, size + thickness, options.wall);
+    }
+}
+
+/// Draws the solution through the cell centers, the first of which is at
+/// `offset`. A jump across a wrapping seam is left out rather than drawn
+/// across the whole maze.
+fn draw_solution(canvas: &mut Canvas, size: f64, offset: (f64, f64), options: &RasterOptions) {
+    let center = |(row, column): (usize, usize)| {
+        (offset.0 + column as f64 * size, offset.1 + row as f64 * size)
+    };
+    let thickness = (size / 4.0).max(1.0);
+    let half = thickness / 2.0;
+
+    for cell in &options.solution {
+        let (x, y) = center(*cell);
+        canvas.fill_polygon(
+            &[(x - half, y - half), (x + half, y - half), (x + half, y + half), (x - half, y + half)],
+            options.solution_color,
+        );
+    }
+    for run in split_at_seams(&options.solution) {
+        for step in run.windows(2) {
+            canvas.line(center(step[0]), center(step[1]), thickness, options.solution_color);
+        }
+    }
+}
+
+/// Draws a square grid; cells a mask turns off are left blank.
+pub fn render(grid: &Grid, options: &RasterOptions) -> Canvas {
+    if let Some(inset) = &options.inset {
+        return render_inset(grid, inset, options);
+    }
+
+    let size = options.cell_size;
+    let mut canvas = Canvas::new(
+        grid.columns * size + options.wall_thickness,
+        grid.rows * size + options.wall_thickness,
+        options.background,
+    );
+
+    let cells = grid.each_cell();
+    let origin = |cell: &Cell| (cell.column * size, cell.row * size);
+    draw_heatmap(&mut canvas, &cells, grid.distance.as_ref(), origin, options);
+
+    // The solution goes under the walls.
+    let middle = (size + options.wall_thickness) as f64 / 2.0;
+    draw_solution(&mut canvas, size as f64, (middle, middle), options);
+
+    for cell in cells {
+        let cell = cell.borrow();
+        let walls = [
+            cell.walled(grid.north(&cell)),
+            cell.walled(grid.south(&cell)),
+            cell.walled(grid.east(&cell)),
+            cell.walled(grid.west(&cell)),
+        ];
+        let (x, y) = origin(&cell);
+        draw_walls(&mut canvas, x, y, walls, options);
+    }
+
+    canvas
+}
+
+/// Draws corridors as bands of floor, in the background or heatmap color,
+/// with everything else left as wall.
+fn render_inset(grid: &Grid, inset: &Inset, options: &RasterOptions) -> Canvas {
+    let size = options.cell_size as f64;
+    let mut canvas = Canvas::new(
+        grid.columns * options.cell_size,
+        grid.rows * options.cell_size,
+        options.wall,
+    );
+
+    let heatmap = match (options.palette, &grid.distance) {
+        (Some(palette), Some(distance)) => Some((palette, distance, distance.max())),
+        _ => None,
+    };
+
+    for ((row, column), shape) in corridors(grid, size, inset) {
+        let floor = heatmap
+            .and_then(|(palette, distance, max)| {
+                let cell = grid.get_cell(row, column)?;
+                Some(palette.distance_color(distance.get(cell)?, max))
+            })
+            .unwrap_or(options.background);
+
+        match shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => canvas.fill_polygon(
+                &[(x, y), (x + width, y), (x + width, y + height), (x, y + height)],
+                floor,
+            ),
+            Shape::Circle { x, y, radius } => canvas.fill_circle((x, y), radius, floor),
+        }
+    }
+
+    draw_solution(&mut canvas, size, (size / 2.0, size / 2.0), options);
+    canvas
+}
+
+/// Draws the levels side by side, lowest first, one cell apart. Stairs are
+/// triangles inside the cell: pointing down on the left, up on the right.
+pub fn render_3d(grid: &Grid3D, options: &RasterOptions) -> Canvas {
+    let size = options.cell_size;
+    let level_width = (grid.columns + 1) * size;
+    let mut canvas = Canvas::new(
+        grid.levels * level_width - size + options.wall_thickness,
+        grid.rows * size + options.wall_thickness,
+        options.background,
+    );
+
+    let cells = grid.each_cell();
+    let origin = |cell: &Cell| {
+        let (level, row) = grid.level_of(cell);
+        (level * level_width + cell.column * size, row * size)
+    };
+    draw_heatmap(&mut canvas, &cells, grid.distance.as_ref(), origin, options);
+
+    for cell in cells {
+        let cell = cell.borrow();
+        let (x, y) = origin(&cell);
+        let walls = [
+            cell.walled(grid.north(&cell)),
+            cell.walled(grid.south(&cell)),
+            cell.walled(grid.east(&cell)),
+            cell.walled(grid.west(&cell)),
+        ];
+        draw_walls(&mut canvas, x, y, walls, options);
+
+        let inset = options.wall_thickness as f64;
+        let (left, top) = (x as f64 + inset, y as f64 + inset);
+        let span = (size as f64 - inset) / 2.0;
+        let middle = left + span;
+        let (third, two_thirds) = (top + span * 2.0 / 3.0, top + span * 4.0 / 3.0);
+
+        if !cell.walled(grid.down(&cell)) {
+            canvas.fill_polygon(
+                &[(left, third), (middle, third), (left + span / 2.0, two_thirds)],
+                options.wall,
+            );
+        }
+        if !cell.walled(grid.up(&cell)) {
+            let right = left + 2.0 * span;
+            canvas.fill_polygon(
+                &[(middle, two_thirds), (right, two_thirds), (middle + span / 2.0, third)],
+                options.wall,
+            );
+        }
+    }
+
+    canvas
+}
+
+/// Draws a polar grid as true rings, with arcs between them.
+pub fn render_polar(grid: &PolarGrid, options: &RasterOptions) -> Canvas {
+    let margin = options.wall_thickness as f64;
+    let layout = geometry::polar(grid, options.cell_size as f64, margin);
+    render_layout(&layout, grid.distance.as_ref(), options)
+}
+
+/// Draws a hex grid as hexagons `cell_size` across.
+pub fn render_hex(grid: &HexGrid, options: &RasterOptions) -> Canvas {
+    let margin = options.wall_thickness as f64;
+    let layout = geometry::hex(grid, options.cell_size as f64, margin);
+    render_layout(&layout, grid.distance.as_ref(), options)
+}
+
+fn render_layout(layout: &Layout, distance: Option<&Distance>, options: &RasterOptions) -> Canvas {
+    let mut canvas = Canvas::new(
+        layout.width.ceil() as usize,
+        layout.height.ceil() as usize,
+        options.background,
+    );
+
+    if let (Some(palette), Some(distance)) = (options.palette, distance) {
+        let max = distance.max();
+        for ((row, column), outline) in &layout.cells {
+            if let Some(value) = distance.cells.get(&Cell::new(*row, *column)) {
+                let points = outline.iter().flat_map(|v| v.points()).collect::<Vec<_>>();
+                canvas.fill_polygon(&points, palette.distance_color(*value, max));
+            }
+        }
+    }
+
+    let solution = options
+        .solution
+        .iter()
+        .filter_map(|v| layout.centers.get(v).copied())
+        .collect::<Vec<_>>();
+    let thickness = (options.cell_size as f64 / 4.0).max(1.0);
+    canvas.polyline(&solution, thickness, options.solution_color);
+
+    for wall in &layout.walls {
+        canvas.polyline(&wall.points(), options.wall_thickness as f64, options.wall);
+    }
+
+    canvas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::Topology;
+
+    fn pair(linked: bool) -> Grid {
+        let mut grid = Grid::new(1, 2);
+        grid.configure_cells();
+        if linked {
+            grid.link(grid.grid[0][0].clone(), grid.grid[0][1].clone());
+        }
+        grid
+    }
+
+    #[test]
+    fn walls_go_where_there_is_no_passage() {
+        let options = RasterOptions::default();
+        for linked in [false, true] {
+            let canvas = render(&pair(linked), &options);
+            assert_eq!((canvas.width, canvas.height), (42, 22));
+            assert_eq!(canvas.get_pixel(0, 10), Some(Color::BLACK));
+            assert_eq!(canvas.get_pixel(10, 0), Some(Color::BLACK));
+            assert_eq!(canvas.get_pixel(10, 10), Some(Color::WHITE));
+            let between = if linked { Color::WHITE } else { Color::BLACK };
+            assert_eq!(canvas.get_pixel(21, 10), Some(between));
+        }
+    }
+
+    #[test]
+    fn heatmap_fills_cells_by_distance() {
+        let mut grid = pair(true);
+        grid.set_distance(Distance::distances(grid.grid[0][0].clone()));
+        let options = RasterOptions {
+            palette: Some(Palette::Grayscale),
+            ..Default::default()
+        };
+        let canvas = render(&grid, &options);
+        assert_eq!(canvas.get_pixel(10, 10), Some(Color::WHITE));
+        assert_eq!(canvas.get_pixel(30, 10), Some(Color::BLACK));
+    }
+}
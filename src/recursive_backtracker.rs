@@ -1,13 +1,17 @@
 #![allow(dead_code)]
 use super::cell::*;
 use super::grid::*;
+use super::random;
 
 use rand::Rng;
 
-pub fn on(grid: &mut Grid) {
-    let mut rng = rand::thread_rng();
+pub fn on<G: MazeGrid>(grid: &mut G) {
+    let mut rng = random::rng();
     let mut stack: Vec<CellLink> = Vec::with_capacity(grid.size());
-    stack.push(grid.get_cell(0, 0).unwrap());
+    match grid.random_cell() {
+        Some(cell) => stack.push(cell),
+        None => return,
+    }
 
     while let Some(current) = stack.last() {
     // loop {
@@ -16,23 +20,36 @@ pub fn on(grid: &mut Grid) {
     //       None => break,
     //     };
       
-        let neighbors = {
-          let cell_borrowed = current.borrow();
-          let neighbors = cell_borrowed
-            .neighbors().clone();
-          neighbors
+        let neighbors = grid
+            .neighbors(current)
             .into_iter()
-            .filter(|v| (*v.clone().borrow()).links.is_empty())
-            .collect::<Vec<_>>()
-        };
+            .filter(|v| v.clone().borrow().links.is_empty())
+            .collect::<Vec<_>>();
         
         if neighbors.is_empty() {
           stack.pop();
         } else {
           let neighbor = neighbors[rng.gen_range(0..neighbors.len())].clone();
-          link(current.clone(), neighbor.clone());
+          grid.link(current.clone(), neighbor.clone());
           stack.push(neighbor.clone());
         }
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::tests::{assert_spanning_tree, assert_spans_every_grid, holed_grid};
+
+    #[test]
+    fn carves_a_spanning_tree_on_every_grid() {
+        assert_spans_every_grid!(super::on);
+    }
+
+    #[test]
+    fn carves_around_masked_cells() {
+        let mut grid = holed_grid();
+        super::on(&mut grid);
+        assert_spanning_tree(&grid, "masked");
+    }
+}
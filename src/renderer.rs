@@ -0,0 +1,272 @@
+#![allow(dead_code)]
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::ascii;
+use super::cell::Cell;
+use super::distance::*;
+use super::grid::*;
+use super::grid3d::*;
+use super::hex_grid::*;
+use super::palette::*;
+use super::polar_grid::*;
+use super::raster::{self, RasterOptions};
+use super::svg::{self, SvgOptions};
+use super::triangle_grid::*;
+use super::unicode;
+use super::weave_grid::*;
+
+/// What to draw, whichever renderer draws it.
+pub struct RenderOptions {
+    /// Characters across a cell for text, pixels or units for images;
+    /// `None` keeps the renderer's own size.
+    pub cell_width: Option<usize>,
+    /// Shows the grid's `distance`: written out in text, as a heatmap in
+    /// images.
+    pub show_distances: bool,
+    /// Cells of a path to highlight, in order; empty for none.
+    pub path: Vec<(usize, usize)>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            cell_width: None,
+            show_distances: true,
+            path: Vec::new(),
+        }
+    }
+}
+
+/// Draws a grid of type `G`; a renderer implements it for every grid
+/// shape it can draw.
+pub trait Renderer<G = Grid> {
+    fn render(&self, grid: &G, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()>;
+
+    fn render_to_file(&self, grid: &G, options: &RenderOptions, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.render(grid, options, &mut out)?;
+        out.flush()
+    }
+
+    /// The rendering as text, for renderers that draw text.
+    fn render_to_string(&self, grid: &G, options: &RenderOptions) -> String {
+        let mut out: Vec<u8> = Vec::new();
+        // Writing to a Vec cannot fail.
+        let _ = self.render(grid, options, &mut out);
+        String::from_utf8_lossy(&out).into_owned()
+    }
+}
+
+/// The text in a text cell `width` columns wide: a path marker, the
+/// distance or nothing, centered.
+fn cell_text(
+    distance: Option<&Distance>,
+    options: &RenderOptions,
+    marker: &str,
+    width: usize,
+    (row, column): (usize, usize),
+) -> String {
+    let text = if options.path.contains(&(row, column)) {
+        String::from(marker)
+    } else {
+        match distance {
+            Some(distance) if options.show_distances => {
+                distance_str(distance.cells.get(&Cell::new(row, column)).copied())
+            }
+            _ => String::new(),
+        }
+    };
+    format!("{:^width$}", text, width = width)
+}
+
+/// `+---+` walls, as the `Debug` output used to draw them.
+pub struct AsciiRenderer;
+
+impl Renderer for AsciiRenderer {
+    fn render(&self, grid: &Grid, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        let width = options.cell_width.unwrap_or(3);
+        let text = ascii::render_with(grid, width, |row, column| {
+            cell_text(grid.distance.as_ref(), options, "*", width, (row, column))
+        });
+        out.write_all(text.as_bytes())
+    }
+}
+
+/// Levels side by side; cells are one column wide between the stairs, so
+/// `cell_width` is ignored.
+impl Renderer<Grid3D> for AsciiRenderer {
+    fn render(&self, grid: &Grid3D, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        let text = ascii::render_3d(grid, |row, column| {
+            cell_text(grid.distance.as_ref(), options, "*", 1, (row, column))
+        });
+        out.write_all(text.as_bytes())
+    }
+}
+
+/// Walls only; triangles have no room for distances or a path.
+impl Renderer<TriangleGrid> for AsciiRenderer {
+    fn render(&self, grid: &TriangleGrid, _: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(ascii::render_triangles(grid).as_bytes())
+    }
+}
+
+impl Renderer<WeaveGrid> for AsciiRenderer {
+    fn render(&self, grid: &WeaveGrid, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        let width = options.cell_width.unwrap_or(3);
+        let text = ascii::render_weave(grid, width, |row, column| {
+            cell_text(grid.grid.distance.as_ref(), options, "*", width, (row, column))
+        });
+        out.write_all(text.as_bytes())
+    }
+}
+
+/// Box-drawing walls, or half blocks for dense output.
+#[derive(Default)]
+pub struct UnicodeRenderer {
+    pub half_blocks: bool,
+}
+
+impl Renderer for UnicodeRenderer {
+    fn render(&self, grid: &Grid, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        // Half blocks draw a fixed one column per cell and nothing inside.
+        let text = if self.half_blocks {
+            unicode::render_half_blocks(grid)
+        } else {
+            let width = options.cell_width.unwrap_or(3);
+            unicode::render_with(grid, width, |row, column| {
+                cell_text(grid.distance.as_ref(), options, "•", width, (row, column))
+            })
+        };
+        out.write_all(text.as_bytes())
+    }
+}
+
+/// PNG images of square, polar and hex grids; the options' colors and
+/// thickness apply, while cell size, heatmap and solution come from the
+/// `RenderOptions`.
+#[derive(Default)]
+pub struct PngRenderer {
+    pub options: RasterOptions,
+}
+
+impl PngRenderer {
+    fn raster_options(&self, options: &RenderOptions) -> RasterOptions {
+        RasterOptions {
+            cell_size: options.cell_width.unwrap_or(self.options.cell_size),
+            palette: options
+                .show_distances
+                .then(|| self.options.palette.unwrap_or(Palette::Viridis)),
+            solution: options.path.clone(),
+            ..self.options
+        }
+    }
+}
+
+impl Renderer for PngRenderer {
+    fn render(&self, grid: &Grid, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        raster::render(grid, &self.raster_options(options)).write_png(out)
+    }
+}
+
+impl Renderer<PolarGrid> for PngRenderer {
+    fn render(&self, grid: &PolarGrid, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        raster::render_polar(grid, &self.raster_options(options)).write_png(out)
+    }
+}
+
+impl Renderer<HexGrid> for PngRenderer {
+    fn render(&self, grid: &HexGrid, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        raster::render_hex(grid, &self.raster_options(options)).write_png(out)
+    }
+}
+
+/// SVG documents of square, polar and hex grids; as with `PngRenderer`,
+/// the options supply the styling.
+#[derive(Default)]
+pub struct SvgRenderer {
+    pub options: SvgOptions,
+}
+
+impl SvgRenderer {
+    fn svg_options(&self, options: &RenderOptions) -> SvgOptions {
+        SvgOptions {
+            cell_size: options
+                .cell_width
+                .map_or(self.options.cell_size, |v| v as f64),
+            palette: options
+                .show_distances
+                .then(|| self.options.palette.unwrap_or(Palette::Viridis)),
+            solution: options.path.clone(),
+            ..self.options
+        }
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn render(&self, grid: &Grid, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(svg::render(grid, &self.svg_options(options)).as_bytes())
+    }
+}
+
+impl Renderer<PolarGrid> for SvgRenderer {
+    fn render(&self, grid: &PolarGrid, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(svg::render_polar(grid, &self.svg_options(options)).as_bytes())
+    }
+}
+
+impl Renderer<HexGrid> for SvgRenderer {
+    fn render(&self, grid: &HexGrid, options: &RenderOptions, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(svg::render_hex(grid, &self.svg_options(options)).as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::Topology;
+
+    fn corridor() -> Grid {
+        let mut grid = Grid::new(1, 3);
+        grid.configure_cells();
+        grid.link(grid.grid[0][0].clone(), grid.grid[0][1].clone());
+        grid.link(grid.grid[0][1].clone(), grid.grid[0][2].clone());
+        grid.set_distance(Distance::distances(grid.grid[0][0].clone()));
+        grid
+    }
+
+    #[test]
+    fn ascii_shows_distances_and_the_path() {
+        let grid = corridor();
+        let options = RenderOptions {
+            path: vec![(0, 2)],
+            ..Default::default()
+        };
+        assert_eq!(
+            AsciiRenderer.render_to_string(&grid, &options),
+            "+---+---+---+\n| 0   1   * |\n+---+---+---+\n"
+        );
+
+        let options = RenderOptions {
+            cell_width: Some(1),
+            show_distances: false,
+            ..Default::default()
+        };
+        assert_eq!(AsciiRenderer.render_to_string(&grid, &options), "+-+-+-+\n|     |\n+-+-+-+\n");
+    }
+
+    #[test]
+    fn images_are_written_whole() {
+        let grid = corridor();
+        let options = RenderOptions::default();
+
+        let mut png = Vec::new();
+        PngRenderer::default().render(&grid, &options, &mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let svg = SvgRenderer::default().render_to_string(&grid, &options);
+        assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+    }
+}
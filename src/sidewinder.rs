@@ -1,38 +1,58 @@
 #![allow(dead_code)]
 use super::cell::*;
 use super::grid::*;
+use super::random;
 
 use rand::Rng;
 
-pub fn on(grid: &mut Grid) {
-    let mut rng = rand::thread_rng();
-    for row in &grid.grid {
+pub fn on<G: MazeGrid>(grid: &mut G) {
+    let mut rng = random::rng();
+    for row in grid.each_row() {
         let mut run: Vec<CellLink> = Vec::new();
-        for cell in row {
+        for cell in &row {
             run.push(cell.clone());
-            let should_close_out = {
-                let cell_borrowed = cell.borrow();
-                cell_borrowed.east.is_none()
-                    || (cell_borrowed.north.is_some() && rng.gen_range(0..2) == 0u8)
-            };
+            let should_close_out = grid.run_next(cell).is_none()
+                || (grid.run_exit(cell).is_some() && rng.gen_range(0..2) == 0u8);
 
             if should_close_out {
-                let member = run[rng.gen_range(0..run.len())].clone();
-                let north = member.borrow().north();
-                match north {
-                    Some(cell2) => {
-                        link(member.clone(), cell2);
-                        run.clear();
-                    },
-                    None => (),
-                }
-            } else {
-                let east = cell.borrow().east();
-                match east {
-                    Some(cell2) => link(cell.clone(), cell2),
-                    None => (),
+                // With a mask some members may have no way out of the run.
+                let members = run
+                    .iter()
+                    .filter(|v| grid.run_exit(v).is_some())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if !members.is_empty() {
+                    let member = members[rng.gen_range(0..members.len())].clone();
+                    if let Some(cell2) = grid.run_exit(&member) {
+                        grid.link(member.clone(), cell2);
+                    }
                 }
+                run.clear();
+            } else if let Some(cell2) = grid.run_next(cell) {
+                grid.link(cell.clone(), cell2);
             }
         }
     }
+
+    // Runs with no way out at all are left stranded; join them on.
+    join_regions(grid);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::tests::{assert_spanning_tree, assert_spans_every_grid, holed_grid};
+
+    #[test]
+    fn carves_a_spanning_tree_on_every_grid() {
+        assert_spans_every_grid!(super::on);
+    }
+
+    #[test]
+    fn carves_around_masked_cells() {
+        for _ in 0..20 {
+            let mut grid = holed_grid();
+            super::on(&mut grid);
+            assert_spanning_tree(&grid, "masked");
+        }
+    }
 }
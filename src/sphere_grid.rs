@@ -0,0 +1,171 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::f64::consts::PI;
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::random;
+use super::topology::*;
+
+/// The surface of a sphere cut into latitude rings, pole to pole. Both
+/// poles are a single cell; towards the equator the rings subdivide like
+/// a `PolarGrid`, and the southern half mirrors the northern one.
+pub struct SphereGrid {
+    pub rows: usize,
+    pub grid: Vec<Vec<CellLink>>,
+    pub distance: Option<Distance>,
+}
+
+impl SphereGrid {
+    pub fn new(rows: usize) -> Self {
+        let angular_height = PI / rows as f64;
+        let half = rows.div_ceil(2);
+
+        let mut counts: Vec<usize> = Vec::with_capacity(rows);
+        for row in 0..half {
+            if row == 0 {
+                counts.push(1);
+                continue;
+            }
+
+            let theta = (row as f64 + 0.5) * angular_height;
+            let circumference = 2.0 * PI * theta.sin();
+            let previous_count = counts[row - 1];
+            let estimated_cell_width = circumference / previous_count as f64;
+            let ratio = (estimated_cell_width / angular_height).round().max(1.0) as usize;
+            counts.push(previous_count * ratio);
+        }
+        for row in half..rows {
+            counts.push(counts[rows - 1 - row]);
+        }
+
+        let grid = counts
+            .iter()
+            .enumerate()
+            .map(|(row, count)| {
+                (0..*count)
+                    .map(|column| Rc::new(RefCell::new(Cell::new(row, column))))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        SphereGrid {
+            rows,
+            grid,
+            distance: None,
+        }
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.distance = Some(distance);
+    }
+
+    /// Columns wrap around the ring.
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<CellLink> {
+        if row >= self.rows {
+            None
+        } else {
+            let count = self.grid[row].len();
+            Some(self.grid[row][column % count].clone())
+        }
+    }
+
+    pub fn cw(&self, cell: &Cell) -> Option<CellLink> {
+        if self.grid[cell.row].len() == 1 {
+            None
+        } else {
+            self.get_cell(cell.row, cell.column + 1)
+        }
+    }
+
+    pub fn ccw(&self, cell: &Cell) -> Option<CellLink> {
+        let count = self.grid[cell.row].len();
+        if count == 1 {
+            None
+        } else {
+            self.get_cell(cell.row, cell.column + count - 1)
+        }
+    }
+
+    /// The cells of the neighboring ring `row` that share an edge with
+    /// `cell`: one when that ring is coarser, several when it is finer.
+    fn across(&self, cell: &Cell, row: usize) -> Vec<CellLink> {
+        let count = self.grid[cell.row].len();
+        let other = self.grid[row].len();
+        if other <= count {
+            let ratio = count / other;
+            vec![self.grid[row][cell.column / ratio].clone()]
+        } else {
+            let ratio = other / count;
+            self.grid[row][cell.column * ratio..(cell.column + 1) * ratio].to_vec()
+        }
+    }
+
+    /// Neighbors in the ring closer to the north pole.
+    pub fn north(&self, cell: &Cell) -> Vec<CellLink> {
+        if cell.row == 0 {
+            Vec::new()
+        } else {
+            self.across(cell, cell.row - 1)
+        }
+    }
+
+    /// Neighbors in the ring closer to the south pole.
+    pub fn south(&self, cell: &Cell) -> Vec<CellLink> {
+        if cell.row + 1 >= self.rows {
+            Vec::new()
+        } else {
+            self.across(cell, cell.row + 1)
+        }
+    }
+}
+
+impl MazeGrid for SphereGrid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.clone()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        if self.rows == 0 {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        let row: usize = rng.gen_range(0..self.rows);
+        let column: usize = rng.gen_range(0..self.grid[row].len());
+        self.get_cell(row, column)
+    }
+
+    fn size(&self) -> usize {
+        self.grid.iter().map(|v| v.len()).sum()
+    }
+}
+
+impl Topology for SphereGrid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        let mut result = [self.cw(cell), self.ccw(cell)]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        result.extend(self.north(cell));
+        result.extend(self.south(cell));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::tests::assert_symmetric;
+
+    #[test]
+    fn neighbors_are_symmetric() {
+        for rows in [2, 3, 7, 10, 16] {
+            let mut grid = SphereGrid::new(rows);
+            grid.configure_cells();
+            assert_symmetric(&grid);
+        }
+    }
+}
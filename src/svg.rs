@@ -0,0 +1,358 @@
+#![allow(dead_code)]
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+use super::canvas::Color;
+use super::cell::Cell;
+use super::distance::*;
+use super::geometry::{self, Layout, Stroke};
+use super::grid::*;
+use super::hex_grid::*;
+use super::inset::*;
+use super::palette::*;
+use super::polar_grid::*;
+use super::voronoi_grid::Point;
+
+pub struct SvgOptions {
+    /// Units from one wall to the next.
+    pub cell_size: f64,
+    pub wall_width: f64,
+    pub background: Color,
+    pub wall: Color,
+    /// Cells marked with a dot, as `(row, column)`.
+    pub start: Option<(usize, usize)>,
+    pub goal: Option<(usize, usize)>,
+    /// Cells the solution passes through, in order.
+    pub solution: Vec<(usize, usize)>,
+    pub solution_color: Color,
+    /// Colors each cell by the grid's `distance`, when it has one.
+    pub palette: Option<Palette>,
+    /// Draws corridors as inset bands instead of walls as lines; the wall
+    /// width then comes from the inset.
+    pub inset: Option<Inset>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            cell_size: 20.0,
+            wall_width: 2.0,
+            background: Color::WHITE,
+            wall: Color::BLACK,
+            start: None,
+            goal: None,
+            solution: Vec::new(),
+            solution_color: SOLUTION,
+            palette: None,
+            inset: None,
+        }
+    }
+}
+
+/// Joins runs of consecutive unit segments into single strokes. `lines[i][j]`
+/// is whether segment `j` of line `i` is drawn; `stroke` turns a line number
+/// and the two ends of a run into path data.
+pub fn merge_runs<F: Fn(usize, usize, usize) -> String>(lines: &[Vec<bool>], stroke: F) -> String {
+    let mut data = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        let mut run_start: Option<usize> = None;
+        for position in 0..=line.len() {
+            let drawn = line.get(position).copied().unwrap_or(false);
+            match (drawn, run_start) {
+                (true, None) => run_start = Some(position),
+                (false, Some(from)) => {
+                    data.push_str(&stroke(index, from, position));
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+    data
+}
+
+fn write_header(svg: &mut String, width: f64, height: f64) {
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+}
+
+fn write_solution(svg: &mut String, data: &str, options: &SvgOptions) {
+    let _ = writeln!(
+        svg,
+        r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+        data,
+        options.solution_color.hex(),
+        options.cell_size / 4.0
+    );
+}
+
+/// Dots on the start and goal cells, wherever `center` puts them.
+fn write_markers<F: Fn((usize, usize)) -> Option<Point>>(
+    svg: &mut String,
+    options: &SvgOptions,
+    center: F,
+) {
+    for (marker, color) in [(options.start, START), (options.goal, GOAL)] {
+        if let Some((x, y)) = marker.and_then(&center) {
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                x,
+                y,
+                options.cell_size / 4.0,
+                color.hex()
+            );
+        }
+    }
+}
+
+/// Draws a square grid as SVG, walls merged into as few strokes as possible.
+pub fn render(grid: &Grid, options: &SvgOptions) -> String {
+    let size = options.cell_size;
+    // Inset corridors are walled in by the background itself.
+    let margin = if options.inset.is_some() {
+        0.0
+    } else {
+        options.wall_width
+    };
+    let width = grid.columns as f64 * size + 2.0 * margin;
+    let height = grid.rows as f64 * size + 2.0 * margin;
+    let center = |(row, column): (usize, usize)| {
+        (
+            margin + (column as f64 + 0.5) * size,
+            margin + (row as f64 + 0.5) * size,
+        )
+    };
+
+    let (horizontal, vertical) = grid.walls();
+    let mut walls = merge_runs(&horizontal, |row, from, to| {
+        format!(
+            "M{} {}H{}",
+            margin + from as f64 * size,
+            margin + row as f64 * size,
+            margin + to as f64 * size
+        )
+    });
+    walls.push_str(&merge_runs(&vertical, |column, from, to| {
+        format!(
+            "M{} {}V{}",
+            margin + column as f64 * size,
+            margin + from as f64 * size,
+            margin + to as f64 * size
+        )
+    }));
+
+    let mut svg = String::new();
+    write_header(&mut svg, width, height);
+    let heatmap = match (options.palette, &grid.distance) {
+        (Some(palette), Some(distance)) => Some((palette, distance, distance.max())),
+        _ => None,
+    };
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        match options.inset {
+            Some(_) => options.wall.hex(),
+            None => options.background.hex(),
+        }
+    );
+
+    if let Some(inset) = &options.inset {
+        for ((row, column), shape) in corridors(grid, size, inset) {
+            let floor = heatmap
+                .and_then(|(palette, distance, max)| {
+                    let cell = grid.get_cell(row, column)?;
+                    Some(palette.distance_color(distance.get(cell)?, max))
+                })
+                .unwrap_or(options.background);
+
+            let _ = match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                } => writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    x,
+                    y,
+                    width,
+                    height,
+                    floor.hex()
+                ),
+                Shape::Circle { x, y, radius } => writeln!(
+                    svg,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                    x,
+                    y,
+                    radius,
+                    floor.hex()
+                ),
+            };
+        }
+    } else if let Some((palette, distance, max)) = heatmap {
+        for cell in grid.each_cell() {
+            if let Some(value) = distance.get(cell.clone()) {
+                let cell = cell.borrow();
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    margin + cell.column as f64 * size,
+                    margin + cell.row as f64 * size,
+                    size,
+                    size,
+                    palette.distance_color(value, max).hex()
+                );
+            }
+        }
+    }
+
+    // The solution goes under the walls.
+    if !options.solution.is_empty() {
+        let mut data = String::new();
+        for run in split_at_seams(&options.solution) {
+            for (index, cell) in run.iter().enumerate() {
+                let (x, y) = center(*cell);
+                data.push_str(&format!("{}{} {}", if index == 0 { "M" } else { "L" }, x, y));
+            }
+        }
+        write_solution(&mut svg, &data, options);
+    }
+
+    if options.inset.is_none() {
+        let _ = writeln!(
+            svg,
+            r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="square"/>"#,
+            walls,
+            options.wall.hex(),
+            options.wall_width
+        );
+    }
+
+    write_markers(&mut svg, options, |v| Some(center(v)));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Path data for `strokes`, joining each onto the last where they meet.
+fn path_data(strokes: &[Stroke]) -> String {
+    let mut data = String::new();
+    let mut position: Option<Point> = None;
+    for stroke in strokes {
+        let (x, y) = stroke.from();
+        let joined =
+            position.is_some_and(|(px, py)| (px - x).abs() < 1e-6 && (py - y).abs() < 1e-6);
+        if !joined {
+            data.push_str(&format!("M{:.2} {:.2}", x, y));
+        }
+
+        match stroke {
+            Stroke::Line(_, (x, y)) => data.push_str(&format!("L{:.2} {:.2}", x, y)),
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => {
+                // One arc command cannot go all the way around, so long
+                // arcs are drawn in two halves.
+                let halves = if (end - start).abs() > PI {
+                    vec![(start + end) / 2.0, *end]
+                } else {
+                    vec![*end]
+                };
+                let sweep = if end > start { 1 } else { 0 };
+                for angle in halves {
+                    let (x, y) = (
+                        center.0 + radius * angle.cos(),
+                        center.1 + radius * angle.sin(),
+                    );
+                    data.push_str(&format!(
+                        "A{:.2} {:.2} 0 0 {} {:.2} {:.2}",
+                        radius, radius, sweep, x, y
+                    ));
+                }
+            }
+        }
+        position = Some(stroke.to());
+    }
+    data
+}
+
+/// Draws a polar grid as true rings, with arcs between them.
+pub fn render_polar(grid: &PolarGrid, options: &SvgOptions) -> String {
+    let layout = geometry::polar(grid, options.cell_size, options.wall_width);
+    render_layout(&layout, grid.distance.as_ref(), options)
+}
+
+/// Draws a hex grid as hexagons `cell_size` across.
+pub fn render_hex(grid: &HexGrid, options: &SvgOptions) -> String {
+    let layout = geometry::hex(grid, options.cell_size, options.wall_width);
+    render_layout(&layout, grid.distance.as_ref(), options)
+}
+
+fn render_layout(layout: &Layout, distance: Option<&Distance>, options: &SvgOptions) -> String {
+    let mut svg = String::new();
+    write_header(&mut svg, layout.width, layout.height);
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        options.background.hex()
+    );
+
+    if let (Some(palette), Some(distance)) = (options.palette, distance) {
+        let max = distance.max();
+        for ((row, column), outline) in &layout.cells {
+            if let Some(value) = distance.cells.get(&Cell::new(*row, *column)) {
+                let _ = writeln!(
+                    svg,
+                    r#"<path d="{}Z" fill="{}"/>"#,
+                    path_data(outline),
+                    palette.distance_color(*value, max).hex()
+                );
+            }
+        }
+    }
+
+    let solution = options
+        .solution
+        .iter()
+        .filter_map(|v| layout.centers.get(v))
+        .enumerate()
+        .map(|(index, (x, y))| format!("{}{:.2} {:.2}", if index == 0 { "M" } else { "L" }, x, y))
+        .collect::<String>();
+    if !solution.is_empty() {
+        write_solution(&mut svg, &solution, options);
+    }
+
+    let _ = writeln!(
+        svg,
+        r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+        path_data(&layout.walls),
+        options.wall.hex(),
+        options.wall_width
+    );
+
+    write_markers(&mut svg, options, |v| layout.centers.get(&v).copied());
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_runs_joins_neighboring_segments() {
+        let lines = vec![
+            vec![true, true, false, true],
+            vec![false, false, false, false],
+            vec![false, true, true, true],
+        ];
+        let data = merge_runs(&lines, |line, from, to| format!("[{} {}-{}]", line, from, to));
+        assert_eq!(data, "[0 0-2][0 3-4][2 1-4]");
+    }
+}
@@ -0,0 +1,67 @@
+#![allow(dead_code)]
+use std::rc::Rc;
+
+use super::cell::*;
+use super::grid::*;
+
+/// The shape of a grid: which cells border which. Generators never see
+/// directions, only the neighbor lists `configure_cells` stores on each
+/// cell (and, for binary tree and sidewinder, the grid's choice among them
+/// in `MazeGrid::run_exit` and `run_next`), so any shape that implements
+/// this works with all of them.
+pub trait Topology: MazeGrid {
+    /// The cells bordering `cell`, in a stable order. On small wrapping
+    /// grids a cell may show up twice, or border itself; both are dropped.
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink>;
+
+    fn configure_cells(&mut self) {
+        for cell in self.each_cell() {
+            let mut neighbors: Vec<CellLink> = Vec::new();
+            for neighbor in self.neighbors_of(&cell.borrow()) {
+                if !Rc::ptr_eq(&cell, &neighbor)
+                    && !neighbors.iter().any(|v| Rc::ptr_eq(v, &neighbor))
+                {
+                    neighbors.push(neighbor);
+                }
+            }
+            cell.borrow_mut().neighbors = neighbors
+                .into_iter()
+                .map(|v| v.downgrade())
+                .collect::<Vec<_>>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::tests::assert_symmetric;
+    use crate::hex_grid::HexGrid;
+    use crate::polar_grid::PolarGrid;
+    use crate::triangle_grid::TriangleGrid;
+    use crate::upsilon_grid::UpsilonGrid;
+
+    fn configured<G: Topology>(mut grid: G) -> G {
+        grid.configure_cells();
+        grid
+    }
+
+    #[test]
+    fn every_shape_borders_symmetrically() {
+        assert_symmetric(&configured(Grid::new(4, 5)));
+        assert_symmetric(&configured(PolarGrid::new(6)));
+        assert_symmetric(&configured(HexGrid::new(4, 5)));
+        assert_symmetric(&configured(TriangleGrid::new(4, 5)));
+        assert_symmetric(&configured(UpsilonGrid::new(4, 5)));
+    }
+
+    #[test]
+    fn neighbor_counts_follow_the_shape() {
+        let count = |cell: CellLink| cell.borrow().neighbors().len();
+        let grid = configured(HexGrid::new(3, 3));
+        assert_eq!(count(grid.get_cell(1, 1).unwrap()), 6);
+        let grid = configured(Grid::new(3, 3));
+        assert_eq!(count(grid.get_cell(1, 1).unwrap()), 4);
+        assert_eq!(count(grid.get_cell(0, 0).unwrap()), 2);
+    }
+}
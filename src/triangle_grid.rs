@@ -0,0 +1,215 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::random;
+use super::topology::*;
+
+/// A grid of triangles ("delta" maze). Cells with an even `row + column`
+/// point up and border the row below; the others point down and border
+/// the row above. Every cell also borders its east and west neighbors.
+pub struct TriangleGrid {
+    pub rows: usize,
+    pub columns: usize,
+    pub grid: Vec<Vec<CellLink>>,
+    pub distance: Option<Distance>,
+}
+
+pub fn upright(cell: &Cell) -> bool {
+    (cell.row + cell.column).is_multiple_of(2)
+}
+
+impl TriangleGrid {
+    /// # Panics
+    ///
+    /// A single column of more than two rows, which falls apart into
+    /// separate pairs of triangles that no maze could join.
+    pub fn new(rows: usize, columns: usize) -> Self {
+        assert!(
+            columns != 1 || rows <= 2,
+            "a triangle grid of one column has no way between its pairs of rows"
+        );
+
+        let grid = (0..rows)
+            .map(|row| {
+                (0..columns)
+                    .map(|column| Rc::new(RefCell::new(Cell::new(row, column))))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        TriangleGrid {
+            rows,
+            columns,
+            grid,
+            distance: None,
+        }
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.distance = Some(distance);
+    }
+
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<CellLink> {
+        if row >= self.rows || column >= self.columns {
+            None
+        } else {
+            Some(self.grid[row][column].clone())
+        }
+    }
+
+    pub fn west(&self, cell: &Cell) -> Option<CellLink> {
+        cell.column
+            .checked_sub(1)
+            .and_then(|column| self.get_cell(cell.row, column))
+    }
+
+    pub fn east(&self, cell: &Cell) -> Option<CellLink> {
+        self.get_cell(cell.row, cell.column + 1)
+    }
+
+    /// Only downward triangles border the row above.
+    pub fn north(&self, cell: &Cell) -> Option<CellLink> {
+        if upright(cell) {
+            None
+        } else {
+            cell.row
+                .checked_sub(1)
+                .and_then(|row| self.get_cell(row, cell.column))
+        }
+    }
+
+    /// Only upward triangles border the row below.
+    pub fn south(&self, cell: &Cell) -> Option<CellLink> {
+        if upright(cell) {
+            self.get_cell(cell.row + 1, cell.column)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the last cell of `row` points up, and so has no way north.
+    fn ends_upright(&self, row: usize) -> bool {
+        self.columns > 1 && (row + self.columns - 1).is_multiple_of(2)
+    }
+}
+
+impl MazeGrid for TriangleGrid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.clone()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        if self.size() == 0 {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        let row: usize = rng.gen_range(0..self.rows);
+        let column: usize = rng.gen_range(0..self.columns);
+        self.get_cell(row, column)
+    }
+
+    fn size(&self) -> usize {
+        self.rows * self.columns
+    }
+
+    /// Only downward triangles have a northern neighbor. A row ending in an
+    /// upward triangle would strand that cell, so the row is cut one cell
+    /// short and the last cell closes towards its west instead.
+    fn run_exit(&self, cell: &CellLink) -> Option<CellLink> {
+        let cell = cell.borrow();
+        if cell.column == self.columns - 1 && self.ends_upright(cell.row) {
+            self.west(&cell)
+        } else {
+            self.north(&cell)
+        }
+    }
+
+    fn run_next(&self, cell: &CellLink) -> Option<CellLink> {
+        let cell = cell.borrow();
+        if cell.column + 2 == self.columns && self.ends_upright(cell.row) {
+            None
+        } else {
+            self.east(&cell)
+        }
+    }
+}
+
+impl Topology for TriangleGrid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        [
+            self.north(cell),
+            self.south(cell),
+            self.east(cell),
+            self.west(cell),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
+
+impl Debug for TriangleGrid {
+    /// The grid's shape; the maze itself is drawn by a `Renderer`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TriangleGrid")
+            .field("rows", &self.rows)
+            .field("columns", &self.columns)
+            .field("has_distance", &self.distance.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::tests::{assert_spanning_tree, assert_symmetric};
+    use crate::{binary_tree, sidewinder};
+
+    fn grid(rows: usize, columns: usize) -> TriangleGrid {
+        let mut grid = TriangleGrid::new(rows, columns);
+        grid.configure_cells();
+        grid
+    }
+
+    #[test]
+    fn runs_span_every_width() {
+        for (rows, columns) in [(1, 1), (2, 1), (5, 2), (6, 2), (5, 8), (6, 8), (5, 9), (6, 9)] {
+            assert_symmetric(&grid(rows, columns));
+
+            let mut tree = grid(rows, columns);
+            binary_tree::on(&mut tree);
+            assert_spanning_tree(&tree, &format!("binary tree {}x{}", rows, columns));
+
+            let mut winder = grid(rows, columns);
+            sidewinder::on(&mut winder);
+            assert_spanning_tree(&winder, &format!("sidewinder {}x{}", rows, columns));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn single_column_is_rejected() {
+        TriangleGrid::new(5, 1);
+    }
+
+    #[test]
+    fn upright_row_ends_close_west() {
+        // Row 1 of four columns ends in an upright cell with no way north.
+        let grid = grid(3, 4);
+        let last = grid.grid[1][3].clone();
+        let exit = grid.run_exit(&last).unwrap();
+        assert_eq!((exit.borrow().row, exit.borrow().column), (1, 2));
+        assert!(grid.run_next(&grid.grid[1][2]).is_none());
+
+        // Row 2 ends pointing down, so it closes north as usual.
+        let exit = grid.run_exit(&grid.grid[2][3]).unwrap();
+        assert_eq!((exit.borrow().row, exit.borrow().column), (1, 3));
+    }
+}
@@ -0,0 +1,187 @@
+#![allow(dead_code)]
+use super::distance::*;
+use super::grid::*;
+
+/// The box-drawing character joining walls that leave a corner in the
+/// given directions.
+fn junction(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (false, false, false, false) => ' ',
+        (true, false, false, false) => '╵',
+        (false, true, false, false) => '╷',
+        (false, false, true, false) => '╴',
+        (false, false, false, true) => '╶',
+        (true, true, false, false) => '│',
+        (false, false, true, true) => '─',
+        (false, true, false, true) => '┌',
+        (false, true, true, false) => '┐',
+        (true, false, false, true) => '└',
+        (true, false, true, false) => '┘',
+        (true, true, false, true) => '├',
+        (true, true, true, false) => '┤',
+        (false, true, true, true) => '┬',
+        (true, false, true, true) => '┴',
+        (true, true, true, true) => '┼',
+    }
+}
+
+/// Draws a square grid with box-drawing walls, in the same layout as
+/// `AsciiRenderer` including the distance overlay.
+pub fn render(grid: &Grid) -> String {
+    render_with(grid, 3, |row, column| {
+        let distance = match (&grid.distance, grid.get_cell(row, column)) {
+            (Some(distance), Some(cell)) => distance_str(distance.get(cell)),
+            _ => String::from(" "),
+        };
+        format!(" {} ", distance)
+    })
+}
+
+/// Draws the box-drawing walls around whatever `contents` puts in each
+/// cell; it should fill `cell_width` columns.
+pub fn render_with<F: Fn(usize, usize) -> String>(
+    grid: &Grid,
+    cell_width: usize,
+    contents: F,
+) -> String {
+    let (horizontal, vertical) = grid.walls();
+    let wall = "─".repeat(cell_width);
+    let open = " ".repeat(cell_width);
+    let corner = |row: usize, column: usize| {
+        let up = row > 0 && vertical[column][row - 1];
+        let down = row < grid.rows && vertical[column][row];
+        let left = column > 0 && horizontal[row][column - 1];
+        let right = column < grid.columns && horizontal[row][column];
+        junction(up, down, left, right)
+    };
+
+    let mut output = String::new();
+    for row in 0..=grid.rows {
+        for (column, wall_above) in horizontal[row].iter().enumerate() {
+            output.push(corner(row, column));
+            output.push_str(if *wall_above { &wall } else { &open });
+        }
+        output.push(corner(row, grid.columns));
+        output.push('\n');
+
+        if row == grid.rows {
+            break;
+        }
+
+        for (column, walls) in vertical.iter().enumerate() {
+            output.push(if walls[row] { '│' } else { ' ' });
+            if column == grid.columns {
+                break;
+            }
+            output.push_str(&contents(row, column));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Draws a square grid at one character per wall or cell across and two
+/// down, using half blocks. Walls are solid; cells and passages are blank.
+pub fn render_half_blocks(grid: &Grid) -> String {
+    let (horizontal, vertical) = grid.walls();
+    let (height, width) = (2 * grid.rows + 1, 2 * grid.columns + 1);
+
+    // Odd coordinates are cells or the passages between them, even ones
+    // walls and the corners where walls meet.
+    let solid = |y: usize, x: usize| -> bool {
+        if y >= height {
+            return false;
+        }
+        let (row, column) = (y / 2, x / 2);
+        match (y % 2, x % 2) {
+            (0, 1) => horizontal[row][column],
+            (1, 0) => vertical[column][row],
+            (0, 0) => {
+                (row > 0 && vertical[column][row - 1])
+                    || (row < grid.rows && vertical[column][row])
+                    || (column > 0 && horizontal[row][column - 1])
+                    || (column < grid.columns && horizontal[row][column])
+            }
+            _ => false,
+        }
+    };
+
+    let mut output = String::new();
+    for y in (0..height).step_by(2) {
+        for x in 0..width {
+            output.push(match (solid(y, x), solid(y + 1, x)) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => ' ',
+            });
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::Topology;
+
+    type Wall = ((usize, usize), (usize, usize));
+
+    /// A 3 x 4 grid open everywhere but between the given pairs of cells.
+    fn walled_off(walls: &[Wall]) -> Grid {
+        let mut grid = Grid::new(3, 4);
+        grid.configure_cells();
+        for cell in grid.each_cell() {
+            let (row, column) = (cell.borrow().row, cell.borrow().column);
+            for other in [(row + 1, column), (row, column + 1)] {
+                let closed = walls.contains(&((row, column), other));
+                if let (false, Some(other)) = (closed, grid.get_cell(other.0, other.1)) {
+                    grid.link(cell.clone(), other);
+                }
+            }
+        }
+        grid
+    }
+
+    fn maze() -> Grid {
+        walled_off(&[
+            ((0, 0), (0, 1)),
+            ((1, 0), (1, 1)),
+            ((0, 0), (1, 0)),
+            ((0, 1), (1, 1)),
+            ((0, 2), (0, 3)),
+            ((1, 2), (2, 2)),
+            ((1, 3), (2, 3)),
+            ((2, 0), (2, 1)),
+        ])
+    }
+
+    #[test]
+    fn joins_walls_with_matching_junctions() {
+        let expected = [
+            "┌─┬───┬─┐",
+            "│ │   │ │",
+            "├─┼─╴ ╵ │",
+            "│ │     │",
+            "│ │ ╶───┤",
+            "│ │     │",
+            "└─┴─────┘",
+        ];
+        let output = render_with(&maze(), 1, |_, _| String::from(" "));
+        assert_eq!(output, expected.join("\n") + "\n");
+    }
+
+    #[test]
+    fn half_blocks_stack_two_rows_per_line() {
+        let expected = [
+            "█▀█▀▀▀█▀█",
+            "█▀█▀▀ ▀ █",
+            "█ █ ▀▀▀▀█",
+            "▀▀▀▀▀▀▀▀▀",
+        ];
+        assert_eq!(render_half_blocks(&maze()), expected.join("\n") + "\n");
+    }
+}
@@ -0,0 +1,134 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::random;
+use super::topology::*;
+
+/// An upsilon grid (octagons and squares) laid out as a checkerboard:
+/// cells with an even `row + column` are octagons bordering four squares
+/// orthogonally and four octagons diagonally, the others are squares
+/// bordering only their four orthogonal octagons.
+pub struct UpsilonGrid {
+    pub rows: usize,
+    pub columns: usize,
+    pub grid: Vec<Vec<CellLink>>,
+    pub distance: Option<Distance>,
+}
+
+pub fn octagon(cell: &Cell) -> bool {
+    (cell.row + cell.column).is_multiple_of(2)
+}
+
+impl UpsilonGrid {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        let grid = (0..rows)
+            .map(|row| {
+                (0..columns)
+                    .map(|column| Rc::new(RefCell::new(Cell::new(row, column))))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        UpsilonGrid {
+            rows,
+            columns,
+            grid,
+            distance: None,
+        }
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.distance = Some(distance);
+    }
+
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<CellLink> {
+        if row >= self.rows || column >= self.columns {
+            None
+        } else {
+            Some(self.grid[row][column].clone())
+        }
+    }
+
+    /// The cell `row_offset` rows and `column_offset` columns away.
+    fn offset(&self, cell: &Cell, row_offset: isize, column_offset: isize) -> Option<CellLink> {
+        let row = cell.row.checked_add_signed(row_offset)?;
+        let column = cell.column.checked_add_signed(column_offset)?;
+        self.get_cell(row, column)
+    }
+
+    pub fn north(&self, cell: &Cell) -> Option<CellLink> {
+        self.offset(cell, -1, 0)
+    }
+
+    pub fn south(&self, cell: &Cell) -> Option<CellLink> {
+        self.offset(cell, 1, 0)
+    }
+
+    pub fn east(&self, cell: &Cell) -> Option<CellLink> {
+        self.offset(cell, 0, 1)
+    }
+
+    pub fn west(&self, cell: &Cell) -> Option<CellLink> {
+        self.offset(cell, 0, -1)
+    }
+
+    /// The diagonal neighbors exist for octagons only.
+    pub fn northeast(&self, cell: &Cell) -> Option<CellLink> {
+        octagon(cell).then(|| self.offset(cell, -1, 1)).flatten()
+    }
+
+    pub fn northwest(&self, cell: &Cell) -> Option<CellLink> {
+        octagon(cell).then(|| self.offset(cell, -1, -1)).flatten()
+    }
+
+    pub fn southeast(&self, cell: &Cell) -> Option<CellLink> {
+        octagon(cell).then(|| self.offset(cell, 1, 1)).flatten()
+    }
+
+    pub fn southwest(&self, cell: &Cell) -> Option<CellLink> {
+        octagon(cell).then(|| self.offset(cell, 1, -1)).flatten()
+    }
+}
+
+impl MazeGrid for UpsilonGrid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.clone()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        if self.size() == 0 {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        let row: usize = rng.gen_range(0..self.rows);
+        let column: usize = rng.gen_range(0..self.columns);
+        self.get_cell(row, column)
+    }
+
+    fn size(&self) -> usize {
+        self.rows * self.columns
+    }
+}
+
+impl Topology for UpsilonGrid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        [
+            self.north(cell),
+            self.south(cell),
+            self.east(cell),
+            self.west(cell),
+            self.northeast(cell),
+            self.northwest(cell),
+            self.southeast(cell),
+            self.southwest(cell),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
@@ -0,0 +1,304 @@
+#![allow(dead_code)]
+use rand::Rng;
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::graph_grid::*;
+use super::grid::*;
+use super::random;
+use super::topology::*;
+
+pub type Point = (f64, f64);
+
+/// A grid of irregular cells around a set of points: each point's cell is
+/// its Voronoi region, and two cells are neighbors when their regions
+/// touch, i.e. when the points share an edge of the Delaunay
+/// triangulation.
+///
+/// Cells are addressed like a polar grid: `row` counts Delaunay edges out
+/// from the point nearest the middle, so every cell but the first has a
+/// neighbor in the row before it, and `column` runs around each ring.
+pub struct VoronoiGrid {
+    pub grid: Vec<Vec<CellLink>>,
+    pub positions: Vec<Vec<Point>>,
+    pub adjacency: Vec<Vec<Vec<(usize, usize)>>>,
+    pub distance: Option<Distance>,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Triangle {
+    vertices: [usize; 3],
+    center: Point,
+    radius_squared: f64,
+}
+
+impl Triangle {
+    fn new(vertices: [usize; 3], points: &[Point]) -> Self {
+        let (ax, ay) = points[vertices[0]];
+        let (bx, by) = points[vertices[1]];
+        let (cx, cy) = points[vertices[2]];
+
+        let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+        let center = if d.abs() < f64::EPSILON {
+            // Degenerate (collinear) triangle: never contains anything.
+            (f64::INFINITY, f64::INFINITY)
+        } else {
+            let a = ax * ax + ay * ay;
+            let b = bx * bx + by * by;
+            let c = cx * cx + cy * cy;
+            (
+                (a * (by - cy) + b * (cy - ay) + c * (ay - by)) / d,
+                (a * (cx - bx) + b * (ax - cx) + c * (bx - ax)) / d,
+            )
+        };
+        let radius_squared = (ax - center.0).powi(2) + (ay - center.1).powi(2);
+
+        Triangle {
+            vertices,
+            center,
+            radius_squared,
+        }
+    }
+
+    fn circumcircle_contains(&self, (x, y): Point) -> bool {
+        (x - self.center.0).powi(2) + (y - self.center.1).powi(2) < self.radius_squared
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a.min(b), a.max(b)), (b.min(c), b.max(c)), (a.min(c), a.max(c))]
+    }
+}
+
+/// The smallest and largest coordinates among `points`.
+pub fn bounds(points: &[Point]) -> (Point, Point) {
+    points.iter().fold(
+        ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN)),
+        |((min_x, min_y), (max_x, max_y)), (x, y)| {
+            ((min_x.min(*x), min_y.min(*y)), (max_x.max(*x), max_y.max(*y)))
+        },
+    )
+}
+
+/// Edges of the Delaunay triangulation of `points`, as index pairs,
+/// using the Bowyer-Watson algorithm.
+pub fn delaunay_edges(points: &[Point]) -> Vec<(usize, usize)> {
+    let count = points.len();
+    if count < 2 {
+        return Vec::new();
+    }
+
+    let ((min_x, min_y), (max_x, max_y)) = bounds(points);
+    let size = (max_x - min_x).max(max_y - min_y).max(1.0);
+    let (mid_x, mid_y) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+
+    // A triangle comfortably enclosing every point; its corners are
+    // appended after the real points and dropped at the end.
+    let mut all = points.to_vec();
+    all.push((mid_x - 20.0 * size, mid_y - size));
+    all.push((mid_x, mid_y + 20.0 * size));
+    all.push((mid_x + 20.0 * size, mid_y - size));
+    let mut triangles = vec![Triangle::new([count, count + 1, count + 2], &all)];
+
+    for (index, point) in points.iter().enumerate() {
+        let (bad, good): (Vec<Triangle>, Vec<Triangle>) = triangles
+            .into_iter()
+            .partition(|v| v.circumcircle_contains(*point));
+        triangles = good;
+
+        // The hole left by the bad triangles is bounded by the edges only
+        // one of them has.
+        let edges = bad.iter().flat_map(|v| v.edges()).collect::<Vec<_>>();
+        for edge in &edges {
+            if edges.iter().filter(|v| *v == edge).count() == 1 {
+                triangles.push(Triangle::new([edge.0, edge.1, index], &all));
+            }
+        }
+    }
+
+    let mut result: Vec<(usize, usize)> = Vec::new();
+    for triangle in triangles {
+        for edge in triangle.edges() {
+            if edge.1 < count && !result.contains(&edge) {
+                result.push(edge);
+            }
+        }
+    }
+
+    // Collinear points have no triangles; chain them in order instead.
+    if result.is_empty() {
+        let mut order = (0..count).collect::<Vec<_>>();
+        order.sort_by(|a, b| points[*a].partial_cmp(&points[*b]).unwrap());
+        result = order
+            .windows(2)
+            .map(|v| (v[0].min(v[1]), v[0].max(v[1])))
+            .collect::<Vec<_>>();
+    }
+
+    result
+}
+
+impl VoronoiGrid {
+    pub fn new(points: Vec<Point>) -> Self {
+        // A repeated point would have no Voronoi region of its own.
+        let mut unique: Vec<Point> = Vec::with_capacity(points.len());
+        for point in points {
+            if !unique.contains(&point) {
+                unique.push(point);
+            }
+        }
+        let points = unique;
+        let count = points.len();
+        let edges = delaunay_edges(&points);
+
+        let mut graph = vec![Vec::new(); count];
+        for (a, b) in &edges {
+            graph[*a].push(*b);
+            graph[*b].push(*a);
+        }
+
+        // Rings of points, each one Delaunay edge further out than the last,
+        // starting from the point nearest the middle.
+        let ((min_x, min_y), (max_x, max_y)) = bounds(&points);
+        let center = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+        let from_center = |(x, y): Point| (x - center.0).powi(2) + (y - center.1).powi(2);
+        let root = (0..count).min_by(|a, b| {
+            from_center(points[*a]).total_cmp(&from_center(points[*b]))
+        });
+
+        let mut rings = root.map(|v| rings(&graph, v)).unwrap_or_default();
+
+        // Within a ring, columns run counterclockwise around the root.
+        let origin = root.map(|v| points[v]).unwrap_or_default();
+        let angle = |(x, y): Point| (y - origin.1).atan2(x - origin.0);
+        let mut address: Vec<(usize, usize)> = vec![(0, 0); count];
+        let mut positions: Vec<Vec<Point>> = Vec::new();
+        for (row, ring) in rings.iter_mut().enumerate() {
+            ring.sort_by(|a, b| angle(points[*a]).total_cmp(&angle(points[*b])));
+            for (column, index) in ring.iter().enumerate() {
+                address[*index] = (row, column);
+            }
+            positions.push(ring.iter().map(|v| points[*v]).collect::<Vec<_>>());
+        }
+
+        let mut adjacency = positions
+            .iter()
+            .map(|v| vec![Vec::new(); v.len()])
+            .collect::<Vec<_>>();
+        for (a, b) in edges {
+            let (a, b) = (address[a], address[b]);
+            adjacency[a.0][a.1].push(b);
+            adjacency[b.0][b.1].push(a);
+        }
+
+        let grid = positions
+            .iter()
+            .enumerate()
+            .map(|(row, ring)| {
+                (0..ring.len())
+                    .map(|column| Rc::new(RefCell::new(Cell::new(row, column))))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        VoronoiGrid {
+            grid,
+            positions,
+            adjacency,
+            distance: None,
+        }
+    }
+
+    /// `count` points scattered uniformly over a `width` x `height` area.
+    pub fn random(count: usize, width: f64, height: f64) -> Self {
+        let mut rng = random::rng();
+        let points = (0..count)
+            .map(|_| (rng.gen_range(0.0..width), rng.gen_range(0.0..height)))
+            .collect::<Vec<_>>();
+        VoronoiGrid::new(points)
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.distance = Some(distance);
+    }
+
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<CellLink> {
+        self.grid.get(row)?.get(column).cloned()
+    }
+
+    pub fn position(&self, cell: &Cell) -> Point {
+        self.positions[cell.row][cell.column]
+    }
+}
+
+impl MazeGrid for VoronoiGrid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.clone()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        let cells = self.each_cell();
+        if cells.is_empty() {
+            return None;
+        }
+
+        let mut rng = random::rng();
+        Some(cells[rng.gen_range(0..cells.len())].clone())
+    }
+
+    fn size(&self) -> usize {
+        self.grid.iter().map(|v| v.len()).sum()
+    }
+
+}
+
+impl Topology for VoronoiGrid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        self.adjacency[cell.row][cell.column]
+            .iter()
+            .filter_map(|(row, column)| self.get_cell(*row, *column))
+            .collect::<Vec<_>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted_edges(points: &[Point]) -> Vec<(usize, usize)> {
+        let mut edges = delaunay_edges(points)
+            .into_iter()
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect::<Vec<_>>();
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn flat_rhombus_splits_along_its_short_diagonal() {
+        // A and B lie outside the circle through C and D, so the
+        // triangulation uses the short diagonal CD and never joins A to B.
+        let points = [(0.0, 0.0), (4.0, 0.0), (2.0, 1.0), (2.0, -1.0)];
+        assert_eq!(
+            sorted_edges(&points),
+            vec![(0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn center_of_a_square_joins_every_corner() {
+        let points = [(0.0, 0.0), (2.0, 0.1), (2.1, 2.0), (0.1, 2.1), (1.0, 1.0)];
+        let edges = sorted_edges(&points);
+        for corner in 0..4 {
+            assert!(edges.contains(&(corner, 4)));
+        }
+        assert_eq!(edges.len(), 8);
+    }
+
+    #[test]
+    fn collinear_points_form_a_chain() {
+        let points = [(3.0, 0.0), (0.0, 0.0), (2.0, 0.0), (1.0, 0.0)];
+        assert_eq!(sorted_edges(&points), vec![(0, 2), (1, 3), (2, 3)]);
+    }
+}
@@ -0,0 +1,194 @@
+#![allow(dead_code)]
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::{cell::RefCell, rc::Rc};
+
+use super::cell::*;
+use super::distance::*;
+use super::grid::*;
+use super::topology::*;
+
+/// A square grid whose passages may cross. A cell can carve straight
+/// under a neighbor that is a plain east-west (or north-south) corridor
+/// to the cell beyond it; the tunnel is a hidden "under" cell linked to
+/// both ends, so `Distance` and the solvers follow it like any passage.
+///
+/// Tunnels are offered by `neighbors`, so only generators that ask for a
+/// cell's neighbors as they carve, like the recursive backtracker, find
+/// them; binary tree and sidewinder never do. `kruskal::weave` lays its
+/// crossings up front instead.
+///
+/// Under cells sit below the grid's own rows: the one beneath `(row,
+/// column)` is numbered `(rows + row, column)`.
+pub struct WeaveGrid {
+    pub grid: Grid,
+    pub under_cells: Vec<CellLink>,
+}
+
+type Step = fn(&Grid, &Cell) -> Option<CellLink>;
+
+impl WeaveGrid {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        WeaveGrid {
+            grid: Grid::new(rows, columns),
+            under_cells: Vec::new(),
+        }
+    }
+
+    pub fn set_distance(&mut self, distance: Distance) {
+        self.grid.set_distance(distance);
+    }
+
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<CellLink> {
+        self.grid.get_cell(row, column)
+    }
+
+    pub fn is_under(&self, cell: &Cell) -> bool {
+        cell.row >= self.grid.rows
+    }
+
+    /// The tunnel beneath the over cell `cell`, if one was dug.
+    pub fn under_cell(&self, cell: &Cell) -> Option<CellLink> {
+        self.under_cells
+            .iter()
+            .find(|v| {
+                let v = v.borrow();
+                v.row == cell.row + self.grid.rows && v.column == cell.column
+            })
+            .cloned()
+    }
+
+    fn linked_towards(&self, cell: &Cell, step: Step) -> bool {
+        match step(&self.grid, cell) {
+            Some(neighbor) => cell.linked(neighbor).is_some(),
+            None => false,
+        }
+    }
+
+    pub fn horizontal_passage(&self, cell: &Cell) -> bool {
+        self.linked_towards(cell, Grid::east)
+            && self.linked_towards(cell, Grid::west)
+            && !self.linked_towards(cell, Grid::north)
+            && !self.linked_towards(cell, Grid::south)
+    }
+
+    pub fn vertical_passage(&self, cell: &Cell) -> bool {
+        self.linked_towards(cell, Grid::north)
+            && self.linked_towards(cell, Grid::south)
+            && !self.linked_towards(cell, Grid::east)
+            && !self.linked_towards(cell, Grid::west)
+    }
+
+    /// The cell two steps from `cell`, if the one in between is a corridor
+    /// across the way and has nothing tunneled under it yet.
+    fn tunnel(&self, cell: &Cell, step: Step) -> Option<CellLink> {
+        let middle = step(&self.grid, cell)?;
+        let middle = middle.borrow();
+        let crosses = if cell.row == middle.row {
+            self.vertical_passage(&middle)
+        } else {
+            self.horizontal_passage(&middle)
+        };
+
+        if crosses && self.under_cell(&middle).is_none() {
+            step(&self.grid, &middle)
+        } else {
+            None
+        }
+    }
+
+    /// Whether there is a passage between the over cells `cell1` and
+    /// `cell2`, on top or through a tunnel under either of them.
+    pub fn connected(&self, cell1: &CellLink, cell2: &CellLink) -> bool {
+        let side1 = [Some(cell1.clone()), self.under_cell(&cell1.borrow())];
+        let side2 = [Some(cell2.clone()), self.under_cell(&cell2.borrow())];
+        side1.iter().flatten().any(|v| {
+            side2
+                .iter()
+                .flatten()
+                .any(|w| v.borrow().linked(w.clone()).is_some())
+        })
+    }
+}
+
+impl MazeGrid for WeaveGrid {
+    fn each_row(&self) -> Vec<Vec<CellLink>> {
+        self.grid.each_row()
+    }
+
+    fn random_cell(&self) -> Option<CellLink> {
+        self.grid.random_cell()
+    }
+
+    fn size(&self) -> usize {
+        self.grid.size()
+    }
+
+    /// Over cells add the cells they can tunnel to.
+    fn neighbors(&self, cell: &CellLink) -> Vec<CellLink> {
+        let cell = cell.borrow();
+        let mut result = cell.neighbors();
+        if self.is_under(&cell) {
+            return result;
+        }
+
+        let steps: [Step; 4] = [Grid::north, Grid::south, Grid::east, Grid::west];
+        for step in steps {
+            if let Some(target) = self.tunnel(&cell, step) {
+                result.push(target);
+            }
+        }
+        result
+    }
+
+    /// Linking to a cell two steps away digs an under cell in between.
+    fn link(&mut self, cell1: CellLink, cell2: CellLink) {
+        let adjacent = cell1
+            .borrow()
+            .neighbors()
+            .iter()
+            .any(|v| Rc::ptr_eq(v, &cell2));
+        if adjacent {
+            link(cell1, cell2);
+            return;
+        }
+
+        let (row, column) = {
+            let (cell1, cell2) = (cell1.borrow(), cell2.borrow());
+            ((cell1.row + cell2.row) / 2, (cell1.column + cell2.column) / 2)
+        };
+        let mut under = Cell::new(self.grid.rows + row, column);
+        under.neighbors = vec![cell1.clone().downgrade(), cell2.clone().downgrade()];
+        let under = Rc::new(RefCell::new(under));
+
+        link(cell1, under.clone());
+        link(under.clone(), cell2);
+        self.under_cells.push(under);
+    }
+
+    fn each_cell(&self) -> Vec<CellLink> {
+        let mut result = self.grid.each_cell();
+        result.extend(self.under_cells.iter().cloned());
+        result
+    }
+}
+
+impl Topology for WeaveGrid {
+    fn neighbors_of(&self, cell: &Cell) -> Vec<CellLink> {
+        self.grid.neighbors_of(cell)
+    }
+
+    fn configure_cells(&mut self) {
+        self.grid.configure_cells();
+    }
+}
+
+impl Debug for WeaveGrid {
+    /// The grid's shape; the maze itself is drawn by a `Renderer`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("WeaveGrid")
+            .field("grid", &self.grid)
+            .field("under_cells", &self.under_cells.len())
+            .finish()
+    }
+}